@@ -14,11 +14,11 @@ pub fn adder(a: u32, b: u32) -> u32 {
         // Compute the carry using AND
         carry = a & b;
         // Adds the bits without considering the carry using XOR
-        a = a ^ b;
+        a ^= b;
         // Shift the carry
         b = carry << 1;
     }
-    return a;
+    a
 }
 
 // Space complexity: O(1)
@@ -34,11 +34,11 @@ pub fn multiplier(a: u32, b: u32) -> u32 {
             result = adder(result, multiplicand);
         }
 
-        multiplicand = multiplicand << 1;
-        multiplier = multiplier >> 1;
+        multiplicand <<= 1;
+        multiplier >>= 1;
     }
 
-    return result;
+    result
 }
 
 // Space complexity: O(1)
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::variable::is_variable_name;
+use crate::{LogicError, Variable};
+
+// Truth values given to a set of variables.
+// Letters 'A'..='Z' live in two bitmasks (bit 0 is 'A'), named variables in a map.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Assignment {
+    assigned: u32,
+    values: u32,
+    named: HashMap<Variable, bool>,
+}
+
+impl Assignment {
+    pub fn new() -> Self {
+        Assignment::default()
+    }
+
+    // Builder form of `set`: Assignment::new().with('A', true).with("door_open", false)
+    pub fn with(mut self, var: impl Into<Variable>, value: bool) -> Self {
+        self.set(var, value);
+        self
+    }
+
+    pub fn set(&mut self, var: impl Into<Variable>, value: bool) {
+        let var = var.into();
+        match var.letter() {
+            Some(letter) => {
+                let bit = 1 << (letter as u32 - 'A' as u32);
+                self.assigned |= bit;
+                if value {
+                    self.values |= bit;
+                } else {
+                    self.values &= !bit;
+                }
+            },
+            None => {
+                self.named.insert(var, value);
+            },
+        }
+    }
+
+    pub fn get(&self, var: &Variable) -> Option<bool> {
+        match var.letter() {
+            Some(letter) => {
+                let bit = 1 << (letter as u32 - 'A' as u32);
+                (self.assigned & bit != 0).then_some(self.values & bit != 0)
+            },
+            None => self.named.get(var).copied(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.assigned.count_ones() as usize + self.named.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Assigned variables and their values, sorted by variable
+    pub fn iter(&self) -> impl Iterator<Item = (Variable, bool)> {
+        let letters = (0..26)
+            .filter(|i| self.assigned & (1 << i) != 0)
+            .map(|i| (Variable::from((b'A' + i as u8) as char), self.values & (1 << i) != 0));
+
+        let mut named: Vec<(Variable, bool)> = self.named.iter().map(|(v, &b)| (v.clone(), b)).collect();
+        named.sort();
+
+        letters.chain(named)
+    }
+}
+
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pairs: Vec<String> = self.iter().map(|(var, value)| format!("{}={}", var, value as u8)).collect();
+        write!(f, "{}", pairs.join(","))
+    }
+}
+
+// Parses comma separated `name=value` pairs where value is 0 or 1: "A=1,B=0,door_open=1"
+impl FromStr for Assignment {
+    type Err = LogicError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut assignment = Assignment::new();
+        if s.trim().is_empty() {
+            return Ok(assignment);
+        }
+
+        let mut start = 0;
+        for pair in s.split(',') {
+            parse_pair(pair, start, &mut assignment)?;
+            start += pair.len() + 1;
+        }
+        Ok(assignment)
+    }
+}
+
+// `offset` is the byte position of `pair` in the whole input, for error reporting
+fn parse_pair(pair: &str, offset: usize, assignment: &mut Assignment) -> Result<(), LogicError> {
+    let invalid = |position: usize| LogicError::InvalidAssignment { position: offset + position };
+
+    let Some((name, value)) = pair.split_once('=') else {
+        return Err(invalid(pair.len()));
+    };
+
+    let value_start = name.len() + 1;
+    let name_start = name.len() - name.trim_start().len();
+    let name = name.trim();
+    if !is_variable_name(name) {
+        return Err(invalid(name_start));
+    }
+
+    let value_offset = value_start + value.len() - value.trim_start().len();
+    match value.trim() {
+        "1" => assignment.set(name, true),
+        "0" => assignment.set(name, false),
+        _ => return Err(invalid(value_offset)),
+    }
+    Ok(())
+}
@@ -0,0 +1,199 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Shl};
+
+// Unsigned integer of any size, for model counts past u128.
+// Little endian base 2^64 digits, without trailing zeros so that each value
+// has a single representation, zero being no digit at all.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    digits: Vec<u64>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        BigUint::default()
+    }
+
+    pub fn one() -> Self {
+        BigUint::from(1u64)
+    }
+
+    // 2^exponent
+    pub fn power_of_two(exponent: usize) -> Self {
+        BigUint::one() << exponent
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        match self.digits[..] {
+            [] => Some(0),
+            [low] => Some(low as u128),
+            [low, high] => Some((high as u128) << 64 | low as u128),
+            _ => None,
+        }
+    }
+
+    // Number of bits, 0 for zero
+    pub fn bits(&self) -> usize {
+        match self.digits.last() {
+            Some(last) => 64 * self.digits.len() - last.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    fn normalized(mut self) -> Self {
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+        self
+    }
+
+    // Divides in place by a single digit, returning the remainder
+    fn divide(&mut self, divisor: u64) -> u64 {
+        let mut remainder = 0u128;
+        for digit in self.digits.iter_mut().rev() {
+            let current = remainder << 64 | *digit as u128;
+            *digit = (current / divisor as u128) as u64;
+            remainder = current % divisor as u128;
+        }
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+        remainder as u64
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        BigUint { digits: vec![value] }.normalized()
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(value: u128) -> Self {
+        BigUint { digits: vec![value as u64, (value >> 64) as u64] }.normalized()
+    }
+}
+
+impl Add<&BigUint> for BigUint {
+    type Output = BigUint;
+
+    fn add(mut self, other: &BigUint) -> BigUint {
+        if self.digits.len() < other.digits.len() {
+            self.digits.resize(other.digits.len(), 0);
+        }
+        let mut carry = false;
+        for (i, digit) in self.digits.iter_mut().enumerate() {
+            let (sum, overflow) = digit.overflowing_add(other.digits.get(i).copied().unwrap_or(0));
+            let (sum, carried) = sum.overflowing_add(carry as u64);
+            *digit = sum;
+            carry = overflow || carried;
+            if !carry && i >= other.digits.len() {
+                break;
+            }
+        }
+        if carry {
+            self.digits.push(1);
+        }
+        self
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: BigUint) -> BigUint {
+        self + &other
+    }
+}
+
+// Schoolbook multiplication
+// Time complexity: O(a . b) for a and b digits
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+        let mut digits = vec![0u64; self.digits.len() + other.digits.len()];
+        for (i, &a) in self.digits.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, &b) in other.digits.iter().enumerate() {
+                let current = digits[i + j] as u128 + a as u128 * b as u128 + carry;
+                digits[i + j] = current as u64;
+                carry = current >> 64;
+            }
+            digits[i + other.digits.len()] = carry as u64;
+        }
+        BigUint { digits }.normalized()
+    }
+}
+
+impl Mul for BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: BigUint) -> BigUint {
+        &self * &other
+    }
+}
+
+impl Shl<usize> for BigUint {
+    type Output = BigUint;
+
+    fn shl(self, shift: usize) -> BigUint {
+        if self.is_zero() {
+            return self;
+        }
+        let (words, bits) = (shift / 64, shift % 64);
+        let mut digits = vec![0u64; words];
+        let mut carry = 0;
+        for digit in self.digits {
+            if bits == 0 {
+                digits.push(digit);
+            } else {
+                digits.push(digit << bits | carry);
+                carry = digit >> (64 - bits);
+            }
+        }
+        digits.push(carry);
+        BigUint { digits }.normalized()
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.digits.len().cmp(&other.digits.len()).then_with(|| self.digits.iter().rev().cmp(other.digits.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Decimal digits, peeled off 19 at a time
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+
+        let mut value = self.clone();
+        let mut chunks = Vec::new();
+        while !value.is_zero() {
+            chunks.push(value.divide(CHUNK));
+        }
+        let Some(first) = chunks.pop() else {
+            return write!(f, "0");
+        };
+        write!(f, "{}", first)?;
+        for chunk in chunks.iter().rev() {
+            write!(f, "{:019}", chunk)?;
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,24 @@
-use ready_set_boole::{build_and_print_expression, conjunctive_normal_form};
+use ready_set_boole::{
+    build_and_print_expression, conjunctive_normal_form_with, simplified_conjunctive_normal_form, CnfEncoding, Expression,
+};
 use std::io::{self, Write};
 
+// --tseitin              linear-size equisatisfiable CNF with auxiliary variables
+// --plaisted-greenbaum   same, keeping only the implications each subformula needs
+// --simplify             drop duplicate, tautological and subsumed clauses, propagate units
+// --dimacs               print the clauses in the DIMACS format of SAT solvers
 pub fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let encoding = if args.iter().any(|arg| arg == "--plaisted-greenbaum") {
+        CnfEncoding::PlaistedGreenbaum
+    } else if args.iter().any(|arg| arg == "--tseitin") {
+        CnfEncoding::Tseitin
+    } else {
+        CnfEncoding::Distributive
+    };
+    let simplify = args.iter().any(|arg| arg == "--simplify");
+    let dimacs = args.iter().any(|arg| arg == "--dimacs");
+
     let mut input = String::new();
 
     loop {
@@ -13,7 +30,21 @@ pub fn main() {
             Ok(_) => {
                 let formula = input.trim();
                 build_and_print_expression(formula);
-                println!("=> {}", conjunctive_normal_form(formula));
+                if dimacs {
+                    match Expression::from_formula(formula).and_then(|expr| expr.to_cnf_with(encoding)) {
+                        Ok(mut cnf) => {
+                            if simplify {
+                                cnf.simplify();
+                            }
+                            print!("{}", cnf.to_dimacs());
+                        },
+                        Err(err) => eprintln!("{}", err.render(formula)),
+                    }
+                } else if simplify {
+                    println!("=> {}", simplified_conjunctive_normal_form(formula, encoding));
+                } else {
+                    println!("=> {}", conjunctive_normal_form_with(formula, encoding));
+                }
                 input.clear();
             },
             Err(e) => {
@@ -0,0 +1,21 @@
+use ready_set_boole::Pla;
+use std::io::{self, Read};
+
+// Minimizes a PLA file, read from the path given as argument or from stdin,
+// and prints the result in the same format
+pub fn main() {
+    let mut text = String::new();
+    let read = match std::env::args().nth(1) {
+        Some(path) => std::fs::read_to_string(&path).map(|content| text = content),
+        None => io::stdin().read_to_string(&mut text).map(|_| ()),
+    };
+    if let Err(e) = read {
+        eprintln!("Error reading input: {}", e);
+        return;
+    }
+
+    match text.parse::<Pla>() {
+        Ok(pla) => print!("{}", pla.minimize()),
+        Err(err) => eprintln!("error: {}", err),
+    }
+}
@@ -0,0 +1,40 @@
+use ready_set_boole::{build_and_print_expression, Expression, KarnaughMap};
+use std::io::{self, Write};
+
+// --groups   outline a minimal cover of the function
+// --svg      print the map as SVG instead of text
+pub fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let groups = args.iter().any(|arg| arg == "--groups");
+    let svg = args.iter().any(|arg| arg == "--svg");
+
+    let mut input = String::new();
+
+    loop {
+        print!("Enter formula: ");
+        io::stdout().flush().unwrap();
+
+        match io::stdin().read_line(& mut input) {
+            Ok(0) => break,
+            Ok(_) => {
+                let formula = input.trim();
+                build_and_print_expression(formula);
+
+                let map = Expression::from_formula(formula).and_then(|expr| KarnaughMap::from_expression(&expr));
+                match map {
+                    Ok(map) => {
+                        let map = if groups { map.with_minimal_cover() } else { map };
+                        print!("{}", if svg { map.to_svg() } else { map.to_text() });
+                    },
+                    Err(err) => eprintln!("{}", err.render(formula)),
+                }
+                println!();
+                input.clear();
+            },
+            Err(e) => {
+                eprintln!("Error reading input: {}", e);
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use ready_set_boole::{build_and_print_expression, minimal_sum_of_products};
+use std::io::{self, Write};
+
+pub fn main() {
+    let mut input = String::new();
+
+    loop {
+        print!("Enter formula: ");
+        io::stdout().flush().unwrap();
+
+        match io::stdin().read_line(& mut input) {
+            Ok(0) => break,
+            Ok(_) => {
+                let formula = input.trim();
+                build_and_print_expression(formula);
+                println!("=> {}", minimal_sum_of_products(formula));
+                input.clear();
+            },
+            Err(e) => {
+                eprintln!("Error reading input: {}", e);
+                break;
+            }
+        }
+    }
+}
@@ -1,7 +1,39 @@
-use ready_set_boole::{build_and_print_expression, truth_table::print_truth_table};
+use ready_set_boole::{
+    build_and_print_expression,
+    truth_table::{print_truth_table_ordered, RowOrder, StepLabel, TableFormat, TruthTable},
+    Expression,
+};
 use std::io::{self, Write};
 
+// --gray        list the rows in Gray code order
+// --steps       add a column per subexpression, labelled in infix
+// --steps=rpn   same, labelled in RPN
+// --dont-care=FORMULA   show '-' on the rows where FORMULA is true, cannot be used with --steps
 pub fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let order = if args.iter().any(|arg| arg == "--gray") { RowOrder::Gray } else { RowOrder::Binary };
+    let steps = if args.iter().any(|arg| arg == "--steps=rpn") {
+        Some(StepLabel::Rpn)
+    } else if args.iter().any(|arg| arg == "--steps") {
+        Some(StepLabel::Infix)
+    } else {
+        None
+    };
+    let dont_care = match args.iter().find_map(|arg| arg.strip_prefix("--dont-care=")) {
+        Some(formula) => match Expression::from_formula(formula) {
+            Ok(expr) => Some(expr),
+            Err(err) => {
+                eprintln!("{}", err.render(formula));
+                return;
+            },
+        },
+        None => None,
+    };
+    if dont_care.is_some() && steps.is_some() {
+        eprintln!("--steps cannot be used with --dont-care");
+        return;
+    }
+
     let mut input = String::new();
 
     loop {
@@ -13,8 +45,19 @@ pub fn main() {
             Ok(_) => {
                 let formula = input.trim();
                 build_and_print_expression(formula);
-                print_truth_table(formula);
-                println!("");
+
+                // Plain tables are streamed, the others need every column first
+                let table = Expression::from_formula(formula).and_then(|expr| match (&dont_care, steps) {
+                    (Some(dont_care), _) => expr.with_dont_care(dont_care.clone()).truth_table().map(Some),
+                    (None, Some(label)) => TruthTable::from_expression_with_steps(&expr, label).map(Some),
+                    (None, None) => Ok(None),
+                });
+                match table {
+                    Ok(Some(table)) => print!("{}", table.render_ordered(TableFormat::Markdown, order)),
+                    Ok(None) => print_truth_table_ordered(formula, order),
+                    Err(err) => eprintln!("{}", err.render(formula)),
+                }
+                println!();
                 input.clear();
             },
             Err(e) => {
@@ -24,4 +67,3 @@ pub fn main() {
         }
     }
 }
-
@@ -1,7 +1,88 @@
-use ready_set_boole::{build_and_print_expression, sat::*};
+use ready_set_boole::{build_and_print_expression, cdcl::Cdcl, model_count::count_models, sat::*, variable::is_variable_name, BigUint, Cnf, Expression, Variable};
 use std::io::{self, Write};
 
+// --dimacs PATH     decides the CNF of a DIMACS file instead of reading formulas,
+//                   followed by the statistics of the solver
+// --all[=N]         prints every model, or the first N
+// --project=A,B     with --all, models restricted to these variables
+// --count           prints the number of models
+// Flags that conflict or would be ignored are errors.
 pub fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(unknown) = args.iter().find(|arg| {
+        arg.starts_with("--")
+            && !matches!(arg.as_str(), "--dimacs" | "--all" | "--count")
+            && !arg.starts_with("--all=")
+            && !arg.starts_with("--project=")
+    }) {
+        eprintln!("error: unknown flag {}", unknown);
+        return;
+    }
+    let count = args.iter().any(|arg| arg == "--count");
+    // Some(limit) in --all mode
+    let mut all: Option<Option<usize>> = None;
+    for arg in &args {
+        if arg == "--all" {
+            all = Some(None);
+        } else if let Some(limit) = arg.strip_prefix("--all=") {
+            match limit.parse() {
+                Ok(limit) => all = Some(Some(limit)),
+                Err(_) => {
+                    eprintln!("error: --all=N expects a number");
+                    return;
+                },
+            }
+        }
+    }
+    let projection: Option<Vec<Variable>> = match args.iter().find_map(|arg| arg.strip_prefix("--project=")) {
+        Some(names) => {
+            let names: Vec<&str> = names.split(',').map(str::trim).filter(|name| !name.is_empty()).collect();
+            if let Some(name) = names.iter().find(|name| !is_variable_name(name)) {
+                eprintln!("error: '{}' is not a variable name", name);
+                return;
+            }
+            Some(names.into_iter().map(Variable::new).collect())
+        },
+        None => None,
+    };
+    let dimacs = args.iter().position(|arg| arg == "--dimacs");
+    let conflict = if projection.is_some() && all.is_none() {
+        Some("--project only applies to --all")
+    } else if dimacs.is_some() && (all.is_some() || projection.is_some() || count) {
+        Some("--dimacs cannot be used with --all, --project or --count")
+    } else if count && all.is_some() {
+        Some("--count cannot be used with --all")
+    } else {
+        None
+    };
+    if let Some(message) = conflict {
+        eprintln!("error: {}", message);
+        return;
+    }
+
+    if let Some(position) = dimacs {
+        let Some(path) = args.get(position + 1) else {
+            eprintln!("error: --dimacs expects a path");
+            return;
+        };
+        match std::fs::read_to_string(path) {
+            Ok(text) => match Cnf::from_dimacs(&text) {
+                Ok(cnf) => {
+                    let mut solver = Cdcl::new(&cnf);
+                    println!("{}", solver.solve().is_some());
+                    let statistics = solver.statistics();
+                    println!("decisions: {}", statistics.decisions);
+                    println!("conflicts: {}", statistics.conflicts);
+                    println!("propagations: {}", statistics.propagations);
+                    println!("restarts: {}", statistics.restarts);
+                },
+                Err(err) => eprintln!("error: {}", err),
+            },
+            Err(e) => eprintln!("Error reading {}: {}", path, e),
+        }
+        return;
+    }
+
     let mut input = String::new();
 
     loop {
@@ -13,7 +94,35 @@ pub fn main() {
             Ok(_) => {
                 let formula = input.trim();
                 build_and_print_expression(formula);
-                println!("=> {}", sat(formula));
+                match Expression::from_formula(formula) {
+                    Ok(expr) if count => {
+                        let count = count_models(&expr);
+                        println!("=> {} model{}", count, if count == BigUint::one() { "" } else { "s" });
+                    },
+                    Ok(expr) if all.is_some() => {
+                        let mut models = match &projection {
+                            Some(projection) => all_models_projected(&expr, projection),
+                            None => all_models(&expr),
+                        };
+                        if let Some(Some(limit)) = all {
+                            models = models.with_limit(limit);
+                        }
+                        let mut count = 0;
+                        for model in models {
+                            println!("{}", model);
+                            count += 1;
+                        }
+                        println!("=> {} model{}", count, if count == 1 { "" } else { "s" });
+                    },
+                    Ok(expr) => match solve(&expr) {
+                        Some(model) => println!("=> true\nmodel: {}", model),
+                        None => println!("=> false"),
+                    },
+                    Err(err) => {
+                        eprintln!("{}", err.render(formula));
+                        println!("=> false");
+                    },
+                }
                 input.clear();
             },
             Err(e) => {
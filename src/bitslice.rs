@@ -0,0 +1,211 @@
+use crate::program::Instruction;
+use crate::{Expression, LogicError, Program};
+
+// Largest truth table materialized as a column: 2^30 rows take 128 MiB
+pub const MAX_BITSLICED_VARIABLES: usize = 30;
+
+// Rows are evaluated 64 at a time, one per bit of a u64 lane
+pub const ROWS_PER_BLOCK: u64 = 64;
+
+// Lane of the variable read from bit k of the row, for the 64 rows of a block:
+// bit j of LOW_PATTERNS[k] is bit k of j
+const LOW_PATTERNS: [u64; 6] = [
+    0xAAAA_AAAA_AAAA_AAAA,
+    0xCCCC_CCCC_CCCC_CCCC,
+    0xF0F0_F0F0_F0F0_F0F0,
+    0xFF00_FF00_FF00_FF00,
+    0xFFFF_0000_FFFF_0000,
+    0xFFFF_FFFF_0000_0000,
+];
+
+// The output column of a truth table, packed 64 rows per word.
+// Row r is bit r % 64 of word r / 64; bits past the last row are always 0.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TruthColumn {
+    words: Vec<u64>,
+    variable_count: usize,
+}
+
+impl TruthColumn {
+    pub fn from_words(words: Vec<u64>, variable_count: usize) -> Self {
+        let mut column = TruthColumn { words, variable_count };
+        column.words.resize(block_count(variable_count) as usize, 0);
+        if let Some(last) = column.words.last_mut() {
+            *last &= valid_rows_mask(variable_count);
+        }
+        column
+    }
+
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    pub fn variable_count(&self) -> usize {
+        self.variable_count
+    }
+
+    // Number of rows, 2^v
+    pub fn len(&self) -> u64 {
+        1 << self.variable_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, row: u64) -> bool {
+        (self.words[(row / ROWS_PER_BLOCK) as usize] >> (row % ROWS_PER_BLOCK)) & 1 == 1
+    }
+
+    pub fn set(&mut self, row: u64, value: bool) {
+        let word = &mut self.words[(row / ROWS_PER_BLOCK) as usize];
+        let bit = 1 << (row % ROWS_PER_BLOCK);
+        if value {
+            *word |= bit;
+        } else {
+            *word &= !bit;
+        }
+    }
+
+    // Number of rows where the column is 1, the model count of the formula
+    pub fn count_ones(&self) -> u64 {
+        self.words.iter().map(|word| word.count_ones() as u64).sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len()).map(|row| self.get(row))
+    }
+}
+
+impl Program {
+    // Evaluates 64 rows at once: lanes[i] holds the 64 values of variable i,
+    // bit j of the result is the value of the formula on the j-th set of values
+    pub fn evaluate_lanes(&self, lanes: &[u64]) -> u64 {
+        let wide: Vec<[u64; 1]> = lanes.iter().map(|&lane| [lane]).collect();
+        self.evaluate_lanes_wide(&wide)[0]
+    }
+
+    // Same as evaluate_lanes over N words per variable, 64 * N rows per pass
+    // m = instruction count
+    // Space complexity: O(N) - the stack depth is bounded by the program, see Program
+    // Time complexity: O(m . N)
+    pub fn evaluate_lanes_wide<const N: usize>(&self, lanes: &[[u64; N]]) -> [u64; N] {
+        let mut stack: Vec<[u64; N]> = Vec::with_capacity(8);
+
+        for instruction in self.instructions() {
+            match *instruction {
+                Instruction::Const(value) => stack.push([if value { u64::MAX } else { 0 }; N]),
+                Instruction::Load(index) => stack.push(lanes[index as usize]),
+                Instruction::Not => {
+                    let top = stack.last_mut().unwrap(); // Programs are well formed
+                    for word in top.iter_mut() {
+                        *word = !*word;
+                    }
+                },
+                binary => {
+                    let b = stack.pop().unwrap(); // Programs are well formed
+                    let a = stack.last_mut().unwrap();
+                    for (a, b) in a.iter_mut().zip(b) {
+                        *a = match binary {
+                            Instruction::And => *a & b,
+                            Instruction::Or => *a | b,
+                            Instruction::Xor => *a ^ b,
+                            Instruction::Implication => !*a | b,
+                            Instruction::ConverseImplication => *a | !b,
+                            _ => !(*a ^ b),
+                        };
+                    }
+                },
+            }
+        }
+
+        stack.pop().unwrap_or([0; N])
+    }
+
+    // Evaluates rows 64 * block .. 64 * block + 63, row 64 * block + j in bit j.
+    // Bits past the last row of the table are 0.
+    pub fn evaluate_block(&self, block: u64) -> u64 {
+        self.evaluate_blocks::<1>(block)[0]
+    }
+
+    // Bit-sliced truth table of the program
+    // n = variable count, m = instruction count
+    // Space complexity: O(2^n / 64)
+    // Time complexity: O(2^n / 64 . m)
+    pub fn truth_column(&self) -> Result<TruthColumn, LogicError> {
+        let n = self.variables().len();
+        if n > MAX_BITSLICED_VARIABLES {
+            return Err(LogicError::TooManyVariables { count: n, max: MAX_BITSLICED_VARIABLES });
+        }
+
+        const WIDE: usize = 8;
+        let blocks = block_count(n);
+        let mut words = Vec::with_capacity(blocks as usize);
+
+        let mut block = 0;
+        while block + WIDE as u64 <= blocks {
+            words.extend(self.evaluate_blocks::<WIDE>(block));
+            block += WIDE as u64;
+        }
+        while block < blocks {
+            words.push(self.evaluate_block(block));
+            block += 1;
+        }
+
+        Ok(TruthColumn::from_words(words, n))
+    }
+
+    fn evaluate_blocks<const N: usize>(&self, first_block: u64) -> [u64; N] {
+        let n = self.variables().len();
+        let lanes: Vec<[u64; N]> = (0..n)
+            .map(|i| std::array::from_fn(|offset| variable_lane(n - 1 - i, first_block + offset as u64)))
+            .collect();
+
+        let mut result = self.evaluate_lanes_wide(&lanes);
+        if n < 6 {
+            result[0] &= valid_rows_mask(n);
+        }
+        result
+    }
+}
+
+impl Expression {
+    // The truth table column of the expression over its sorted variables
+    pub fn evaluate_bitsliced(&self) -> Result<TruthColumn, LogicError> {
+        Program::compile(self)?.truth_column()
+    }
+
+    // Same value on every row, checked 64 rows at a time without
+    // materializing either truth table
+    // v = variable count of both expressions, m = size of both expressions
+    // Space complexity: O(m + v)
+    // Time complexity: O(2^v / 64 . m)
+    pub fn is_equivalent(&self, other: &Expression) -> Result<bool, LogicError> {
+        let mut variables: Vec<_> = self.variables().union(&other.variables()).cloned().collect();
+        variables.sort();
+
+        let left = Program::compile_with_variables(self, &variables)?;
+        let right = Program::compile_with_variables(other, &variables)?;
+        Ok((0..block_count(variables.len())).all(|block| left.evaluate_block(block) == right.evaluate_block(block)))
+    }
+}
+
+// Lane of the variable read from `bit` of the row index, within `block`
+fn variable_lane(bit: usize, block: u64) -> u64 {
+    if bit < 6 {
+        LOW_PATTERNS[bit]
+    } else if (block >> (bit - 6)) & 1 == 1 {
+        u64::MAX
+    } else {
+        0
+    }
+}
+
+pub(crate) fn block_count(variable_count: usize) -> u64 {
+    if variable_count >= 6 { 1 << (variable_count - 6) } else { 1 }
+}
+
+// Rows of the last block that belong to the table
+fn valid_rows_mask(variable_count: usize) -> u64 {
+    if variable_count >= 6 { u64::MAX } else { (1 << (1 << variable_count)) - 1 }
+}
@@ -0,0 +1,530 @@
+use std::collections::HashSet;
+
+use crate::cnf::{Clause, Cnf, Literal};
+use crate::Expression;
+
+// Conflicts before the first restart, scaled by the Luby sequence
+const RESTART_UNIT: u64 = 100;
+
+// Variable activity decay: bumps grow by 1 / VARIABLE_DECAY after each conflict
+const VARIABLE_DECAY: f64 = 0.95;
+
+const CLAUSE_DECAY: f64 = 0.999;
+
+// Activities are scaled down past this value to stay in range
+const RESCALE_LIMIT: f64 = 1e100;
+
+// Counters of a search, accumulated over the calls to solve
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Statistics {
+    pub decisions: u64,
+    pub conflicts: u64,
+    // Literals whose consequences were propagated
+    pub propagations: u64,
+    pub restarts: u64,
+    pub learned_clauses: u64,
+    pub deleted_clauses: u64,
+}
+
+// Conflict-driven clause learning solver:
+// - two watched literals: a clause is only visited when one of its two watched
+//   literals becomes false, and nothing is undone when backtracking
+// - on a conflict, the clause learned is the first unique implication point
+//   cut of the implication graph, and the search jumps back to the second
+//   highest level of the clause, where it becomes unit
+// - decisions pick the unassigned variable with the highest activity, bumped
+//   for the variables of each conflict by an exponentially growing amount
+//   (EVSIDS), with the value it last had (phase saving)
+// - restarts follow the Luby sequence, keeping activities and learned clauses
+// - the least active half of the learned clauses is dropped once they
+//   outnumber a bound growing with each reduction
+// Returns a model from solve, values[i] being the value of variable i.
+pub struct Cdcl {
+    clauses: Vec<ClauseData>,
+    // Clauses watching each literal, by Literal::index
+    watches: Vec<Vec<usize>>,
+    values: Vec<Option<bool>>,
+    levels: Vec<usize>,
+    // Clause that propagated each variable, None for decisions and level 0 units
+    reasons: Vec<Option<usize>>,
+    trail: Vec<Literal>,
+    // Trail length at the start of each decision level
+    trail_limits: Vec<usize>,
+    // Trail entries before this one have been propagated
+    propagated: usize,
+    activities: Vec<f64>,
+    bump: f64,
+    clause_bump: f64,
+    phases: Vec<bool>,
+    order: Heap,
+    // Level 0 units of the input, enqueued by solve
+    units: Vec<Literal>,
+    // An empty clause or a conflict at level 0 was found
+    unsatisfiable: bool,
+    max_learned: usize,
+    // Learned clauses not deleted
+    learned_count: usize,
+    statistics: Statistics,
+}
+
+struct ClauseData {
+    literals: Clause,
+    learned: bool,
+    activity: f64,
+    deleted: bool,
+}
+
+impl Cdcl {
+    pub fn new(cnf: &Cnf) -> Self {
+        let variable_count = cnf.variables().len();
+        let mut solver = Cdcl {
+            clauses: Vec::new(),
+            watches: vec![Vec::new(); 2 * variable_count],
+            values: vec![None; variable_count],
+            levels: vec![0; variable_count],
+            reasons: vec![None; variable_count],
+            trail: Vec::new(),
+            trail_limits: Vec::new(),
+            propagated: 0,
+            activities: vec![0.0; variable_count],
+            bump: 1.0,
+            clause_bump: 1.0,
+            phases: vec![false; variable_count],
+            order: Heap::new(variable_count),
+            units: Vec::new(),
+            unsatisfiable: false,
+            max_learned: cnf.clauses().len() / 3 + 1000,
+            learned_count: 0,
+            statistics: Statistics::default(),
+        };
+        for variable in 0..variable_count {
+            solver.order.insert(variable, &solver.activities);
+        }
+        for clause in cnf.clauses() {
+            solver.add_clause(clause.clone());
+        }
+        solver
+    }
+
+    // Solver of the Tseitin encoding, the variables of the expression first
+    pub fn from_expression(expr: &Expression) -> Self {
+        Cdcl::new(expr.to_tseitin().cnf())
+    }
+
+    pub fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+
+    // Adds a clause over the variables of the CNF, also between calls to solve
+    pub fn add_clause(&mut self, mut clause: Clause) {
+        self.backtrack(0);
+        let mut seen = HashSet::new();
+        clause.retain(|literal| seen.insert(*literal));
+        // Level 0 assignments left by a previous solve are permanent
+        if clause.iter().any(|&literal| seen.contains(&!literal) || self.value(literal) == Some(true)) {
+            return;
+        }
+        clause.retain(|&literal| self.value(literal).is_none());
+        match clause[..] {
+            [] => self.unsatisfiable = true,
+            [unit] => self.units.push(unit),
+            _ => {
+                self.attach(clause, false);
+            },
+        }
+    }
+
+    pub fn solve(&mut self) -> Option<Vec<bool>> {
+        self.backtrack(0);
+        if self.unsatisfiable {
+            return None;
+        }
+        for unit in std::mem::take(&mut self.units) {
+            match self.value(unit) {
+                Some(true) => {},
+                Some(false) => self.unsatisfiable = true,
+                None => self.enqueue(unit, None),
+            }
+            // Kept so they hold again after the next backtrack to level 0
+            self.units.push(unit);
+        }
+        if self.unsatisfiable {
+            return None;
+        }
+
+        let mut restart = 0;
+        loop {
+            let budget = luby(restart) * RESTART_UNIT;
+            match self.search(budget) {
+                Some(true) => {
+                    return Some(self.values.iter().map(|value| value.unwrap_or(false)).collect());
+                },
+                Some(false) => {
+                    self.unsatisfiable = true;
+                    return None;
+                },
+                None => {
+                    restart += 1;
+                    self.statistics.restarts += 1;
+                    self.backtrack(0);
+                },
+            }
+        }
+    }
+
+    // Some(satisfiable), or None once `budget` conflicts call for a restart
+    fn search(&mut self, budget: u64) -> Option<bool> {
+        let mut conflicts = 0;
+        loop {
+            if let Some(conflict) = self.propagate() {
+                self.statistics.conflicts += 1;
+                conflicts += 1;
+                if self.trail_limits.is_empty() {
+                    return Some(false);
+                }
+                let (learned, level) = self.analyze(conflict);
+                self.backtrack(level);
+                match learned[..] {
+                    [unit] => {
+                        self.units.push(unit);
+                        self.enqueue(unit, None);
+                    },
+                    _ => {
+                        let asserting = learned[0];
+                        let index = self.attach(learned, true);
+                        self.bump_clause(index);
+                        self.enqueue(asserting, Some(index));
+                    },
+                }
+                self.statistics.learned_clauses += 1;
+                self.decay();
+                continue;
+            }
+
+            if conflicts >= budget {
+                return None;
+            }
+            if self.learned_count >= self.max_learned + self.trail.len() {
+                self.reduce();
+            }
+            let Some(variable) = self.next_variable() else {
+                return Some(true);
+            };
+            self.statistics.decisions += 1;
+            self.trail_limits.push(self.trail.len());
+            self.enqueue(Literal::new(variable, !self.phases[variable]), None);
+        }
+    }
+
+    fn value(&self, literal: Literal) -> Option<bool> {
+        self.values[literal.variable()].map(|value| literal.value(value))
+    }
+
+    fn level(&self) -> usize {
+        self.trail_limits.len()
+    }
+
+    fn enqueue(&mut self, literal: Literal, reason: Option<usize>) {
+        let variable = literal.variable();
+        self.values[variable] = Some(!literal.is_negated());
+        self.levels[variable] = self.level();
+        self.reasons[variable] = reason;
+        self.trail.push(literal);
+    }
+
+    // Watches the first two literals, returns the clause index
+    fn attach(&mut self, literals: Clause, learned: bool) -> usize {
+        let index = self.clauses.len();
+        self.watches[literals[0].index()].push(index);
+        self.watches[literals[1].index()].push(index);
+        self.clauses.push(ClauseData { literals, learned, activity: 0.0, deleted: false });
+        self.learned_count += usize::from(learned);
+        index
+    }
+
+    // Undoes the assignments above `level`, saving their values as phases
+    fn backtrack(&mut self, level: usize) {
+        if self.level() <= level {
+            return;
+        }
+        let length = self.trail_limits[level];
+        for literal in self.trail.drain(length..) {
+            let variable = literal.variable();
+            self.values[variable] = None;
+            self.reasons[variable] = None;
+            self.phases[variable] = !literal.is_negated();
+            self.order.insert(variable, &self.activities);
+        }
+        self.trail_limits.truncate(level);
+        self.propagated = length;
+    }
+
+    // Each clause keeps its watched literals in positions 0 and 1. When one
+    // becomes false, another literal not false takes its place, otherwise the
+    // clause is unit on the other watch, or a conflict if that one is false too.
+    // Returns the conflicting clause.
+    fn propagate(&mut self) -> Option<usize> {
+        while self.propagated < self.trail.len() {
+            let falsified = !self.trail[self.propagated];
+            self.propagated += 1;
+            self.statistics.propagations += 1;
+
+            let watchers = std::mem::take(&mut self.watches[falsified.index()]);
+            let mut kept = Vec::with_capacity(watchers.len());
+            let mut conflict = None;
+
+            for (position, &index) in watchers.iter().enumerate() {
+                if conflict.is_some() {
+                    kept.extend_from_slice(&watchers[position..]);
+                    break;
+                }
+                if self.clauses[index].deleted {
+                    continue;
+                }
+                let literals = &mut self.clauses[index].literals;
+                if literals[0] == falsified {
+                    literals.swap(0, 1);
+                }
+                let other = literals[0];
+                if self.values[other.variable()].map(|value| other.value(value)) == Some(true) {
+                    kept.push(index);
+                    continue;
+                }
+
+                let replacement = (2..literals.len()).find(|&k| {
+                    let literal = literals[k];
+                    self.values[literal.variable()].map(|value| literal.value(value)) != Some(false)
+                });
+                match replacement {
+                    Some(k) => {
+                        literals.swap(1, k);
+                        let watched = literals[1];
+                        self.watches[watched.index()].push(index);
+                    },
+                    None => {
+                        kept.push(index);
+                        match self.value(other) {
+                            Some(false) => conflict = Some(index),
+                            _ => self.enqueue(other, Some(index)),
+                        }
+                    },
+                }
+            }
+
+            self.watches[falsified.index()] = kept;
+            if conflict.is_some() {
+                return conflict;
+            }
+        }
+        None
+    }
+
+    // First unique implication point: resolves the conflict with the reasons
+    // of the literals of the current level, latest first, until a single one
+    // is left. Returns the learned clause, its asserting literal first and a
+    // literal of the backjump level second, with that level.
+    fn analyze(&mut self, conflict: usize) -> (Clause, usize) {
+        let mut seen = vec![false; self.values.len()];
+        let mut learned: Clause = vec![Literal::positive(0)];
+        let mut pending = 0;
+        let mut index = self.trail.len();
+        let mut clause = conflict;
+        let mut resolved: Option<Literal> = None;
+
+        loop {
+            if self.clauses[clause].learned {
+                self.bump_clause(clause);
+            }
+            // The first literal of a reason is the one it implied
+            let skip = usize::from(resolved.is_some());
+            for position in skip..self.clauses[clause].literals.len() {
+                let literal = self.clauses[clause].literals[position];
+                let variable = literal.variable();
+                if seen[variable] || self.levels[variable] == 0 {
+                    continue;
+                }
+                seen[variable] = true;
+                self.bump_variable(variable);
+                if self.levels[variable] == self.level() {
+                    pending += 1;
+                } else {
+                    learned.push(literal);
+                }
+            }
+
+            loop {
+                index -= 1;
+                if seen[self.trail[index].variable()] {
+                    break;
+                }
+            }
+            let literal = self.trail[index];
+            seen[literal.variable()] = false;
+            pending -= 1;
+            if pending == 0 {
+                learned[0] = !literal;
+                break;
+            }
+            resolved = Some(literal);
+            clause = self.reasons[literal.variable()].unwrap(); // Only the UIP can be a decision
+        }
+
+        let mut level = 0;
+        if learned.len() > 1 {
+            let highest = (1..learned.len()).max_by_key(|&k| self.levels[learned[k].variable()]).unwrap();
+            learned.swap(1, highest);
+            level = self.levels[learned[1].variable()];
+        }
+        (learned, level)
+    }
+
+    fn next_variable(&mut self) -> Option<usize> {
+        while let Some(variable) = self.order.pop(&self.activities) {
+            if self.values[variable].is_none() {
+                return Some(variable);
+            }
+        }
+        None
+    }
+
+    fn bump_variable(&mut self, variable: usize) {
+        self.activities[variable] += self.bump;
+        if self.activities[variable] > RESCALE_LIMIT {
+            for activity in &mut self.activities {
+                *activity /= RESCALE_LIMIT;
+            }
+            self.bump /= RESCALE_LIMIT;
+        }
+        self.order.increase(variable, &self.activities);
+    }
+
+    fn bump_clause(&mut self, index: usize) {
+        self.clauses[index].activity += self.clause_bump;
+        if self.clauses[index].activity > RESCALE_LIMIT {
+            for clause in self.clauses.iter_mut().filter(|clause| clause.learned) {
+                clause.activity /= RESCALE_LIMIT;
+            }
+            self.clause_bump /= RESCALE_LIMIT;
+        }
+    }
+
+    fn decay(&mut self) {
+        self.bump /= VARIABLE_DECAY;
+        self.clause_bump /= CLAUSE_DECAY;
+    }
+
+    // Deletes the least active half of the learned clauses, keeping binary
+    // ones and the reasons of current assignments
+    fn reduce(&mut self) {
+        let mut candidates: Vec<usize> = (0..self.clauses.len())
+            .filter(|&index| {
+                let clause = &self.clauses[index];
+                clause.learned && !clause.deleted && clause.literals.len() > 2 && !self.is_reason(index)
+            })
+            .collect();
+        candidates.sort_by(|&a, &b| self.clauses[a].activity.total_cmp(&self.clauses[b].activity));
+        for &index in &candidates[..candidates.len() / 2] {
+            self.clauses[index].deleted = true;
+            self.clauses[index].literals = Clause::new();
+            self.statistics.deleted_clauses += 1;
+            self.learned_count -= 1;
+        }
+        self.max_learned += self.max_learned / 10;
+    }
+
+    fn is_reason(&self, index: usize) -> bool {
+        let first = self.clauses[index].literals[0];
+        self.reasons[first.variable()] == Some(index) && self.value(first) == Some(true)
+    }
+}
+
+// Convenience wrapper like dpll
+pub fn cdcl(cnf: &Cnf) -> Option<Vec<bool>> {
+    Cdcl::new(cnf).solve()
+}
+
+// 1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8, ... for i = 0, 1, ...
+fn luby(i: u64) -> u64 {
+    let mut i = i + 1;
+    loop {
+        // Smallest k with i <= 2^k - 1
+        let k = 64 - i.leading_zeros() as u64;
+        if i == (1 << k) - 1 {
+            return 1 << (k - 1);
+        }
+        i -= (1 << (k - 1)) - 1;
+    }
+}
+
+// Binary max-heap of variables ordered by activity, with the position of each
+// variable to raise it in place after a bump
+struct Heap {
+    items: Vec<usize>,
+    positions: Vec<Option<usize>>,
+}
+
+impl Heap {
+    fn new(variable_count: usize) -> Self {
+        Heap { items: Vec::with_capacity(variable_count), positions: vec![None; variable_count] }
+    }
+
+    fn insert(&mut self, variable: usize, activities: &[f64]) {
+        if self.positions[variable].is_some() {
+            return;
+        }
+        self.items.push(variable);
+        self.positions[variable] = Some(self.items.len() - 1);
+        self.sift_up(self.items.len() - 1, activities);
+    }
+
+    fn increase(&mut self, variable: usize, activities: &[f64]) {
+        if let Some(position) = self.positions[variable] {
+            self.sift_up(position, activities);
+        }
+    }
+
+    fn pop(&mut self, activities: &[f64]) -> Option<usize> {
+        let top = *self.items.first()?;
+        let last = self.items.pop().unwrap();
+        self.positions[top] = None;
+        if !self.items.is_empty() {
+            self.items[0] = last;
+            self.positions[last] = Some(0);
+            self.sift_down(0, activities);
+        }
+        Some(top)
+    }
+
+    fn sift_up(&mut self, mut position: usize, activities: &[f64]) {
+        while position > 0 {
+            let parent = (position - 1) / 2;
+            if activities[self.items[parent]] >= activities[self.items[position]] {
+                break;
+            }
+            self.swap(parent, position);
+            position = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut position: usize, activities: &[f64]) {
+        loop {
+            let mut largest = position;
+            for child in [2 * position + 1, 2 * position + 2] {
+                if child < self.items.len() && activities[self.items[child]] > activities[self.items[largest]] {
+                    largest = child;
+                }
+            }
+            if largest == position {
+                break;
+            }
+            self.swap(position, largest);
+            position = largest;
+        }
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.items.swap(a, b);
+        self.positions[self.items[a]] = Some(a);
+        self.positions[self.items[b]] = Some(b);
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Not;
+
+use crate::conjunctive_normal_form::CnfEncoding;
+use crate::synthesis::right_fold;
+use crate::{Expression, LogicError, Variable};
+
+// A variable of a Cnf or its negation, packed as 2 * index + negated where
+// index is the position of the variable in Cnf::variables.
+// The packed form doubles as an index into per-literal arrays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Literal(u32);
+
+impl Literal {
+    pub fn new(variable: usize, negated: bool) -> Self {
+        Literal((variable as u32) << 1 | negated as u32)
+    }
+
+    pub fn positive(variable: usize) -> Self {
+        Literal::new(variable, false)
+    }
+
+    pub fn negative(variable: usize) -> Self {
+        Literal::new(variable, true)
+    }
+
+    pub fn variable(self) -> usize {
+        (self.0 >> 1) as usize
+    }
+
+    pub fn is_negated(self) -> bool {
+        self.0 & 1 == 1
+    }
+
+    // 2 * variable + negated
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    // Value of the literal when its variable is `value`
+    pub fn value(self, value: bool) -> bool {
+        value != self.is_negated()
+    }
+}
+
+impl Not for Literal {
+    type Output = Literal;
+
+    fn not(self) -> Literal {
+        Literal(self.0 ^ 1)
+    }
+}
+
+// A disjunction of literals, false when empty
+pub type Clause = Vec<Literal>;
+
+// A conjunction of clauses over named variables, true when empty
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cnf {
+    variables: Vec<Variable>,
+    clauses: Vec<Clause>,
+}
+
+impl Expression {
+    // The clauses of the expression, over its sorted variables for the
+    // distributive encoding, followed by auxiliary ones for the others.
+    // Only the distributive encoding can fail, past DEFAULT_CNF_LIMIT nodes.
+    pub fn to_cnf_with(&self, encoding: CnfEncoding) -> Result<Cnf, LogicError> {
+        match encoding {
+            CnfEncoding::Distributive => Cnf::from_expression(&self.to_cnf()?),
+            CnfEncoding::Tseitin => Ok(self.to_tseitin().into_cnf()),
+            CnfEncoding::PlaistedGreenbaum => Ok(self.to_plaisted_greenbaum().into_cnf()),
+        }
+    }
+}
+
+impl Cnf {
+    pub fn new(variables: Vec<Variable>) -> Self {
+        Cnf { variables, clauses: Vec::new() }
+    }
+
+    // Reads an expression already in CNF: a tree of ANDs over clauses, each a
+    // tree of ORs over variables, negated variables and constants.
+    // A true constant satisfies its clause, which is dropped, a false one is
+    // dropped from its clause.
+    pub fn from_expression(expr: &Expression) -> Result<Self, LogicError> {
+        let mut cnf = Cnf::new(expr.sorted_variables());
+        let indices: HashMap<Variable, usize> = cnf.variables.iter().cloned().zip(0..).collect();
+        let mut clauses = Vec::new();
+        collect(expr, true, &mut clauses);
+
+        for clause in clauses {
+            let mut literals = Vec::new();
+            collect(clause, false, &mut literals);
+
+            let mut result = Clause::new();
+            let mut satisfied = false;
+            for literal in literals {
+                match literal {
+                    Expression::Val(value) => satisfied |= *value,
+                    Expression::Neg(inner) if matches!(**inner, Expression::Val(_)) => {
+                        satisfied |= **inner == Expression::Val(false);
+                    },
+                    Expression::Var(v) => result.push(Literal::positive(indices[v])),
+                    Expression::Neg(inner) => match &**inner {
+                        Expression::Var(v) => result.push(Literal::negative(indices[v])),
+                        _ => return Err(LogicError::UnexpectedOperatorCNF),
+                    },
+                    _ => return Err(LogicError::UnexpectedOperatorCNF),
+                }
+            }
+            if !satisfied {
+                cnf.add_clause(result);
+            }
+        }
+        Ok(cnf)
+    }
+
+    pub fn variables(&self) -> &[Variable] {
+        &self.variables
+    }
+
+    pub fn clauses(&self) -> &[Clause] {
+        &self.clauses
+    }
+
+    // Linear in the variable count, keep a map of the indices for repeated lookups
+    pub fn variable_index(&self, var: &Variable) -> Option<usize> {
+        self.variables.iter().position(|known| known == var)
+    }
+
+    // Returns the index of the new variable
+    pub fn add_variable(&mut self, var: Variable) -> usize {
+        self.variables.push(var);
+        self.variables.len() - 1
+    }
+
+    pub fn add_clause(&mut self, clause: Clause) {
+        debug_assert!(clause.iter().all(|literal| literal.variable() < self.variables.len()));
+        self.clauses.push(clause);
+    }
+
+    // Value of the formula, values[i] being the value of variable i
+    pub fn evaluate(&self, values: &[bool]) -> bool {
+        self.clauses
+            .iter()
+            .all(|clause| clause.iter().any(|literal| literal.value(values[literal.variable()])))
+    }
+
+    // Right-associative conjunction of right-associative disjunctions, the
+    // shape produced by conjunctive_normal_form
+    pub fn to_expression(&self) -> Expression {
+        let clauses = self.clauses.iter().map(|clause| {
+            let literals = clause.iter().map(|&literal| self.literal_expression(literal));
+            right_fold(literals.collect(), Expression::Or, false)
+        });
+        right_fold(clauses.collect(), Expression::And, true)
+    }
+
+    pub fn literal_expression(&self, literal: Literal) -> Expression {
+        let var = Expression::var(self.variables[literal.variable()].clone());
+        if literal.is_negated() { !var } else { var }
+    }
+
+    pub fn to_rpn(&self) -> String {
+        self.to_expression().to_rpn()
+    }
+
+    // The formula is false: it holds an empty clause
+    pub fn has_empty_clause(&self) -> bool {
+        self.clauses.iter().any(Vec::is_empty)
+    }
+
+    // Passes keeping the formula equivalent, repeated until none applies:
+    // duplicates and tautologies, unit propagation and subsumption.
+    // Pure literal elimination is left out as it only preserves satisfiability.
+    // k = clause count, l = literals per clause
+    // Time complexity: O(k^2 . l) per round, at most one round per variable
+    pub fn simplify(&mut self) {
+        self.remove_duplicates();
+        self.remove_tautologies();
+        loop {
+            let before = self.clauses.clone();
+            self.propagate_units();
+            self.remove_subsumed();
+            if self.clauses == before {
+                break;
+            }
+        }
+    }
+
+    // Repeated literals within a clause and repeated clauses, keeping the
+    // first occurrence
+    pub fn remove_duplicates(&mut self) {
+        for clause in &mut self.clauses {
+            let mut seen = HashSet::new();
+            clause.retain(|literal| seen.insert(*literal));
+        }
+        let mut seen = HashSet::new();
+        self.clauses.retain(|clause| {
+            let mut key = clause.clone();
+            key.sort();
+            seen.insert(key)
+        });
+    }
+
+    // Clauses holding a literal and its negation are always true
+    pub fn remove_tautologies(&mut self) {
+        self.clauses.retain(|clause| !clause.iter().any(|literal| clause.contains(&!*literal)));
+    }
+
+    // Each unit clause fixes its literal: other clauses holding it are
+    // satisfied and dropped, its negation is removed from the others. The unit
+    // clauses themselves are kept so the formula stays equivalent.
+    // Contradicting units leave a single empty clause.
+    pub fn propagate_units(&mut self) {
+        let mut assigned: HashSet<Literal> = HashSet::new();
+
+        loop {
+            let found = self.clauses.iter().find_map(|clause| match clause[..] {
+                [literal] if !assigned.contains(&literal) => Some(literal),
+                _ => None,
+            });
+            let Some(unit) = found else {
+                break;
+            };
+            if assigned.contains(&!unit) {
+                self.clauses = vec![Clause::new()];
+                return;
+            }
+            assigned.insert(unit);
+
+            self.clauses.retain(|clause| !clause.contains(&unit) || clause.len() == 1);
+            for clause in &mut self.clauses {
+                clause.retain(|literal| *literal != !unit);
+            }
+            if self.has_empty_clause() {
+                self.clauses = vec![Clause::new()];
+                return;
+            }
+        }
+    }
+
+    // A variable appearing with a single sign can be set to satisfy all its
+    // clauses, which are dropped. The result is only equisatisfiable.
+    pub fn eliminate_pure_literals(&mut self) {
+        loop {
+            let literals: HashSet<Literal> = self.clauses.iter().flatten().copied().collect();
+            let pure: HashSet<Literal> = literals.iter().copied().filter(|literal| !literals.contains(&!*literal)).collect();
+            if pure.is_empty() {
+                break;
+            }
+            self.clauses.retain(|clause| !clause.iter().any(|literal| pure.contains(literal)));
+        }
+    }
+
+    // A clause containing all the literals of another one is implied by it
+    pub fn remove_subsumed(&mut self) {
+        let mut order: Vec<usize> = (0..self.clauses.len()).collect();
+        order.sort_by_key(|&index| self.clauses[index].len());
+
+        let mut kept: Vec<usize> = Vec::new();
+        for index in order {
+            let clause = &self.clauses[index];
+            if !kept.iter().any(|&smaller| self.clauses[smaller].iter().all(|literal| clause.contains(literal))) {
+                kept.push(index);
+            }
+        }
+        // Keep the original clause order
+        kept.sort();
+        self.clauses = kept.into_iter().map(|index| std::mem::take(&mut self.clauses[index])).collect();
+    }
+}
+
+// Operands of the top-level ANDs (is_and) or ORs of an expression
+fn collect<'a>(expr: &'a Expression, is_and: bool, operands: &mut Vec<&'a Expression>) {
+    match (expr, is_and) {
+        (Expression::And(a, b), true) | (Expression::Or(a, b), false) => {
+            collect(a, is_and, operands);
+            collect(b, is_and, operands);
+        },
+        _ => operands.push(expr),
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{normalize, Expression};
+use crate::{normalize, Expression, LogicError};
 
 // Space complexity: O(2^m):
 //      Expression tree - O(m)
@@ -13,50 +13,182 @@ use crate::{normalize, Expression};
 //      OR op: distributing can lead to exponential growth
 //      Flattening: O(m)
 pub fn conjunctive_normal_form(formula: &str) -> String {
-    let expression = match Expression::from_formula(formula) {
+    match try_conjunctive_normal_form(formula) {
         Ok(value) => value,
         Err(err) => {
-            eprintln!("Error occurred while evaluating: {err:?}");
-            return String::new();
+            eprintln!("{}", err.render(formula));
+            String::new()
         }
-    };
-    // First convert to nnf
-    let nnf = normalize(&expression);
-    let cnf = to_cnf(&nnf);
-    flatten_expression(&cnf).to_rpn()
+    }
 }
 
-fn to_cnf(expr: &Expression) -> Expression {
-    match expr {
-        Expression::Val(_) | Expression::Var(_) | Expression::Neg(_) => expr.clone(),
-        Expression::And(a, b) => to_cnf(a) & to_cnf(b),
-        Expression::Or(a, b) => {
-            let a_cnf = to_cnf(a);
-            let b_cnf = to_cnf(b);
-
-            // Apply distributive law: A ∨ (B ∧ C) ⇔ (A ∨ B) ∧ (A ∨ C)
-            match(&a_cnf, &b_cnf) {
-                // if right side is AND, distributive: A ∨ (B ∧ C) ⇔ (A ∨ B) ∧ (A ∨ C)
-                (_, Expression::And(b1, b2)) => {
-                    // use to_cnf on both sides ?
-                    to_cnf(  &((a_cnf.clone() | to_cnf(b1)) & (a_cnf | to_cnf(b2))) )
-                },
-                // If left side is AND, distribute: (A ∧ B) ∨ C ⇔ (A ∨ C) ∧ (B ∨ C)
-                (Expression::And(a1, a2), _) => {
-                    to_cnf( &((to_cnf(a1) | b_cnf.clone()) & (to_cnf(a2) | b_cnf)))
-                },
-                // Base case: both sides are litterals in CNF form
-                _ => a_cnf | b_cnf,
-            }
+pub fn try_conjunctive_normal_form(formula: &str) -> Result<String, LogicError> {
+    Ok(Expression::from_formula(formula)?.to_cnf()?.to_rpn())
+}
+
+// Node count past which the distributive conversion gives up
+pub const DEFAULT_CNF_LIMIT: usize = 1 << 20;
+
+impl Expression {
+    // Equivalent CNF built by distributing OR over AND, a right-associative
+    // conjunction of right-associative disjunctions of literals.
+    // Fails with CnfTooLarge when the result grows past DEFAULT_CNF_LIMIT nodes.
+    pub fn to_cnf(&self) -> Result<Expression, LogicError> {
+        self.to_cnf_limited(DEFAULT_CNF_LIMIT)
+    }
+
+    // Same as to_cnf, giving up past `limit` nodes
+    pub fn to_cnf_limited(&self, limit: usize) -> Result<Expression, LogicError> {
+        // First convert to nnf
+        let nnf = normalize(self);
+        let cnf = to_cnf(&nnf, limit)?;
+        Ok(flatten_expression(&cnf))
+    }
+}
+
+// How conjunctive_normal_form_with builds the CNF
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CnfEncoding {
+    // Distributes OR over AND: an equivalent formula, possibly exponentially larger
+    #[default]
+    Distributive,
+    // Expression::to_tseitin: linear size, equisatisfiable, with auxiliary variables
+    Tseitin,
+    // Expression::to_plaisted_greenbaum: Tseitin keeping only the needed implications
+    PlaistedGreenbaum,
+}
+
+pub fn conjunctive_normal_form_with(formula: &str, encoding: CnfEncoding) -> String {
+    match try_conjunctive_normal_form_with(formula, encoding) {
+        Ok(value) => value,
+        Err(err) => {
+            eprintln!("{}", err.render(formula));
+            String::new()
+        }
+    }
+}
+
+pub fn try_conjunctive_normal_form_with(formula: &str, encoding: CnfEncoding) -> Result<String, LogicError> {
+    match encoding {
+        CnfEncoding::Distributive => try_conjunctive_normal_form(formula),
+        _ => Ok(Expression::from_formula(formula)?.to_cnf_with(encoding)?.to_rpn()),
+    }
+}
+
+// The clauses of the formula after Cnf::simplify
+pub fn simplified_conjunctive_normal_form(formula: &str, encoding: CnfEncoding) -> String {
+    match try_simplified_conjunctive_normal_form(formula, encoding) {
+        Ok(value) => value,
+        Err(err) => {
+            eprintln!("{}", err.render(formula));
+            String::new()
+        }
+    }
+}
+
+pub fn try_simplified_conjunctive_normal_form(formula: &str, encoding: CnfEncoding) -> Result<String, LogicError> {
+    let mut cnf = Expression::from_formula(formula)?.to_cnf_with(encoding)?;
+    cnf.simplify();
+    Ok(cnf.to_rpn())
+}
+
+// CNF of an expression in NNF, failing on other operators or once an
+// intermediate expression grows past `limit` nodes
+pub(crate) fn to_cnf(expr: &Expression, limit: usize) -> Result<Expression, LogicError> {
+    distribute(expr, NormalForm::Conjunctive, limit).map(|(cnf, _)| cnf)
+}
+
+// The two normal forms built by distribute, which only differ by the roles
+// of AND and OR
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NormalForm {
+    // AND of ORs
+    Conjunctive,
+    // OR of ANDs
+    Disjunctive,
+}
+
+impl NormalForm {
+    // Operands of the operator joining clauses (CNF) or terms (DNF)
+    fn outer_operands(self, expr: &Expression) -> Option<(&Expression, &Expression)> {
+        match (self, expr) {
+            (NormalForm::Conjunctive, Expression::And(a, b)) | (NormalForm::Disjunctive, Expression::Or(a, b)) => Some((a, b)),
+            _ => None,
+        }
+    }
+
+    fn outer(self, a: Expression, b: Expression) -> Expression {
+        match self {
+            NormalForm::Conjunctive => a & b,
+            NormalForm::Disjunctive => a | b,
+        }
+    }
+
+    fn inner(self, a: Expression, b: Expression) -> Expression {
+        match self {
+            NormalForm::Conjunctive => a | b,
+            NormalForm::Disjunctive => a & b,
+        }
+    }
+
+    fn too_large(self, limit: usize) -> LogicError {
+        match self {
+            NormalForm::Conjunctive => LogicError::CnfTooLarge { limit },
+            NormalForm::Disjunctive => LogicError::DnfTooLarge { limit },
+        }
+    }
+
+    fn unexpected_operator(self) -> LogicError {
+        match self {
+            NormalForm::Conjunctive => LogicError::UnexpectedOperatorCNF,
+            NormalForm::Disjunctive => LogicError::UnexpectedOperatorDNF,
+        }
+    }
+}
+
+// The normal form with its node count. Written for the CNF below, the DNF
+// swaps AND and OR.
+pub(crate) fn distribute(expr: &Expression, form: NormalForm, limit: usize) -> Result<(Expression, usize), LogicError> {
+    let within = |size: usize| if size > limit { Err(form.too_large(limit)) } else { Ok(size) };
+
+    let (a, b, is_outer) = match expr {
+        Expression::Val(_) | Expression::Var(_) => return Ok((expr.clone(), within(1)?)),
+        Expression::Neg(inner) if matches!(**inner, Expression::Val(_) | Expression::Var(_)) => return Ok((expr.clone(), within(2)?)),
+        Expression::And(a, b) => (a, b, form == NormalForm::Conjunctive),
+        Expression::Or(a, b) => (a, b, form == NormalForm::Disjunctive),
+        // These expressions do not occur after calling normalize()
+        Expression::Neg(_) | Expression::Xor(_, _) | Expression::Implication(_, _) | Expression::Equivalence(_, _) => {
+            return Err(form.unexpected_operator());
+        },
+    };
+
+    let (a_form, a_size) = distribute(a, form, limit)?;
+    let (b_form, b_size) = distribute(b, form, limit)?;
+    if is_outer {
+        return Ok((form.outer(a_form, b_form), within(a_size + b_size + 1)?));
+    }
+
+    // Apply distributive law: A ∨ (B ∧ C) ⇔ (A ∨ B) ∧ (A ∨ C)
+    match (form.outer_operands(&a_form), form.outer_operands(&b_form)) {
+        // if right side is AND, distributive: A ∨ (B ∧ C) ⇔ (A ∨ B) ∧ (A ∨ C)
+        // Both sides are already in normal form, the copies of A double its size
+        (_, Some((b1, b2))) => {
+            within(2 * a_size + b_size + 2)?;
+            let distributed = form.outer(form.inner(a_form.clone(), b1.clone()), form.inner(a_form.clone(), b2.clone()));
+            distribute(&distributed, form, limit)
+        },
+        // If left side is AND, distribute: (A ∧ B) ∨ C ⇔ (A ∨ C) ∧ (B ∨ C)
+        (Some((a1, a2)), None) => {
+            within(a_size + 2 * b_size + 2)?;
+            let distributed = form.outer(form.inner(a1.clone(), b_form.clone()), form.inner(a2.clone(), b_form.clone()));
+            distribute(&distributed, form, limit)
         },
-        // These expressions should not occur after calling normalize()
-        Expression::Xor(_, _) => panic!("Unexpected XOR in CNF conversion"),
-        Expression::Implication(_, _) => panic!("Unexpected IMPLICATION in CNF conversion"),
-        Expression::Equivalence(_, _) => panic!("Unexpected EQUIVALENCE in CNF conversion"),
+        // Base case: both sides are litterals in CNF form
+        (None, None) => Ok((form.inner(a_form, b_form), within(a_size + b_size + 1)?)),
     }
 }
 
-fn flatten_expression(expr: &Expression) -> Expression {
+pub(crate) fn flatten_expression(expr: &Expression) -> Expression {
     match expr {
         Expression::And(_, _) => {
             let mut operands = Vec::new();
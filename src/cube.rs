@@ -0,0 +1,124 @@
+use crate::synthesis::right_fold;
+use crate::{Expression, Variable};
+
+// A product term over n variables, the set of rows it covers.
+// Bit n - 1 - i of `mask` is set when variable i appears in the product, the
+// same bit of `value` then says whether it appears plain (1) or negated (0).
+// Bits of `value` outside `mask` are always 0.
+//
+// With (A, B, C): mask 0b101, value 0b100 is A & !C, covering rows 4 and 6.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Cube {
+    pub mask: u64,
+    pub value: u64,
+}
+
+impl Cube {
+    // The cube of every row
+    pub const UNIVERSE: Cube = Cube { mask: 0, value: 0 };
+
+    pub fn new(mask: u64, value: u64) -> Self {
+        Cube { mask, value: value & mask }
+    }
+
+    // The single row `row` of a table over `variable_count` variables
+    pub fn minterm(row: u64, variable_count: usize) -> Self {
+        Cube::new(full_mask(variable_count), row)
+    }
+
+    pub fn covers(&self, row: u64) -> bool {
+        row & self.mask == self.value
+    }
+
+    // Every row of `other` is a row of self
+    pub fn contains(&self, other: &Cube) -> bool {
+        self.mask & other.mask == self.mask && other.value & self.mask == self.value
+    }
+
+    pub fn literal_count(&self) -> u32 {
+        self.mask.count_ones()
+    }
+
+    // Merges two cubes differing in a single literal: A & B and A & !B give A
+    pub fn combine(&self, other: &Cube) -> Option<Cube> {
+        let difference = self.value ^ other.value;
+        if self.mask != other.mask || difference.count_ones() != 1 {
+            return None;
+        }
+        Some(Cube::new(self.mask & !difference, self.value))
+    }
+
+    // Rows covered by both cubes, None if they share none
+    pub fn intersect(&self, other: &Cube) -> Option<Cube> {
+        if (self.value ^ other.value) & self.mask & other.mask != 0 {
+            return None;
+        }
+        Some(Cube { mask: self.mask | other.mask, value: self.value | other.value })
+    }
+
+    // Self restricted to the rows of `other`, with the variables of `other`
+    // removed: the cube of the Shannon cofactor. None if they are disjoint.
+    pub fn cofactor(&self, other: &Cube) -> Option<Cube> {
+        self.intersect(other)?;
+        Some(Cube { mask: self.mask & !other.mask, value: self.value & !other.mask })
+    }
+
+    // Smallest cube containing both
+    pub fn supercube(&self, other: &Cube) -> Cube {
+        let mask = self.mask & other.mask & !(self.value ^ other.value);
+        Cube { mask, value: self.value & mask }
+    }
+
+    // Rows covered by the cube, in increasing order
+    pub fn rows(&self, variable_count: usize) -> impl Iterator<Item = u64> {
+        let free = full_mask(variable_count) & !self.mask;
+        let value = self.value;
+        // Enumerate the subsets of the free bits: s -> (s - free) & free
+        let mut next = Some(0u64);
+        std::iter::from_fn(move || {
+            let subset = next?;
+            let following = subset.wrapping_sub(free) & free;
+            next = if following == 0 { None } else { Some(following) };
+            Some(value | subset)
+        })
+    }
+
+    // The conjunction of the literals, true for an empty cube
+    pub fn to_product(&self, variables: &[Variable]) -> Expression {
+        let n = variables.len();
+        let literals = variables
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (self.mask >> (n - 1 - i)) & 1 == 1)
+            .map(|(i, var)| {
+                let literal = Expression::var(var.clone());
+                if (self.value >> (n - 1 - i)) & 1 == 1 { literal } else { !literal }
+            });
+        right_fold(literals.collect(), Expression::And, true)
+    }
+
+    // The disjunction of the negated literals, false exactly on the rows of the cube
+    pub fn to_clause(&self, variables: &[Variable]) -> Expression {
+        let n = variables.len();
+        let literals = variables
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (self.mask >> (n - 1 - i)) & 1 == 1)
+            .map(|(i, var)| {
+                let literal = Expression::var(var.clone());
+                if (self.value >> (n - 1 - i)) & 1 == 1 { !literal } else { literal }
+            });
+        right_fold(literals.collect(), Expression::Or, false)
+    }
+}
+
+// Sum of products of a cover, false when empty
+pub fn cover_to_expression(cover: &[Cube], variables: &[Variable]) -> Expression {
+    let products = cover.iter().map(|cube| cube.to_product(variables));
+    right_fold(products.collect(), Expression::Or, false)
+}
+
+// Mask with one bit per variable
+pub fn full_mask(variable_count: usize) -> u64 {
+    if variable_count >= 64 { u64::MAX } else { (1 << variable_count) - 1 }
+}
@@ -8,7 +8,7 @@ pub fn map(x: u16, y: u16) -> f64 {
         packed |= ((x >> i) & 1) << (2 * i);
         packed |= ((y >> i) & 1) << (2 * i + 1)
     }
-    return (packed as f64) / (u32::MAX as f64);
+    (packed as f64) / (u32::MAX as f64)
 }
 
 // Space complexity: O(1)
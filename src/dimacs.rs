@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::cnf::{Clause, Cnf, Literal};
+use crate::conjunctive_normal_form::CnfEncoding;
+use crate::variable::{default_names, is_variable_name};
+use crate::{Expression, LogicError, Variable};
+
+// The DIMACS CNF format read by most SAT solvers:
+//
+//   c var 1 A       comments, these ones naming the variables
+//   c var 2 B
+//   p cnf 2 2       variable and clause counts
+//   1 -2 0          clauses: variable numbers, negative when negated, ending with 0
+//   2 0
+//
+// Variables are numbered from 1 in the order of Cnf::variables. The "c var"
+// comments are written by to_dimacs so names survive a round trip, other
+// comments are ignored. Unnamed variables are called A..Z when there are at
+// most 26 of them, x1, x2, ... after their number otherwise.
+// Clauses may span several lines, and the "%" line ending SATLIB benchmarks
+// stops the reading.
+impl Cnf {
+    pub fn from_dimacs(text: &str) -> Result<Self, LogicError> {
+        let malformed = |line: usize, message: &str| LogicError::Malformed { line, message: message.to_string() };
+
+        // Variable and clause counts, with the line of the header
+        let mut header: Option<(usize, usize, usize)> = None;
+        let mut names: Vec<(usize, Variable, usize)> = Vec::new();
+        let mut clauses: Vec<Clause> = Vec::new();
+        let mut clause = Clause::new();
+        let mut last_line = 0;
+
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            last_line = line_number;
+            let line = line.trim();
+            let words: Vec<&str> = line.split_whitespace().collect();
+
+            match words[..] {
+                [] => continue,
+                ["%", ..] => break,
+                ["c", "var", number, name] => {
+                    let number = number.parse().map_err(|_| malformed(line_number, "expected a variable number"))?;
+                    if !is_variable_name(name) {
+                        return Err(malformed(line_number, &format!("'{}' is not a variable name", name)));
+                    }
+                    names.push((number, Variable::new(name), line_number));
+                },
+                _ if line.starts_with('c') => continue,
+                ["p", ..] => {
+                    if header.is_some() {
+                        return Err(malformed(line_number, "duplicate problem line"));
+                    }
+                    let [_, "cnf", variables, count] = words[..] else {
+                        return Err(malformed(line_number, "expected 'p cnf <variables> <clauses>'"));
+                    };
+                    let variables = variables.parse().map_err(|_| malformed(line_number, "expected a variable count"))?;
+                    let count = count.parse().map_err(|_| malformed(line_number, "expected a clause count"))?;
+                    header = Some((variables, count, line_number));
+                },
+                _ => {
+                    let (variables, _, _) = header.ok_or_else(|| malformed(line_number, "clause before the problem line"))?;
+                    for word in words {
+                        let value: i64 = word.parse().map_err(|_| malformed(line_number, &format!("unexpected '{}'", word)))?;
+                        if value == 0 {
+                            clauses.push(std::mem::take(&mut clause));
+                            continue;
+                        }
+                        let number = value.unsigned_abs() as usize;
+                        if number > variables {
+                            return Err(malformed(
+                                line_number,
+                                &format!("variable {} out of range, the problem line declares {}", number, variables),
+                            ));
+                        }
+                        clause.push(Literal::new(number - 1, value < 0));
+                    }
+                },
+            }
+        }
+
+        let (variables, count, header_line) = header.ok_or_else(|| malformed(last_line.max(1), "missing problem line"))?;
+        // Many instances omit the 0 after the last clause
+        if !clause.is_empty() {
+            clauses.push(clause);
+        }
+        if clauses.len() != count {
+            return Err(malformed(header_line, &format!("the problem line declares {} clauses, found {}", count, clauses.len())));
+        }
+
+        // Numbered from 1 like the DIMACS variables
+        let mut variable_names = default_names(variables, 1);
+        let mut named: HashMap<Variable, usize> = HashMap::new();
+        for (number, name, line) in names {
+            if number == 0 || number > variables {
+                return Err(malformed(line, &format!("variable {} out of range, the problem line declares {}", number, variables)));
+            }
+            if let Some(other) = named.insert(name.clone(), number).filter(|&other| other != number) {
+                return Err(malformed(line, &format!("'{}' already names variable {}", name, other)));
+            }
+            variable_names[number - 1] = name;
+        }
+        // A default name taken by another variable falls back to its number
+        for (index, name) in variable_names.iter_mut().enumerate() {
+            if named.get(name).is_some_and(|&number| number != index + 1) {
+                *name = Variable::new(&format!("x{}", index + 1));
+            }
+        }
+
+        let mut cnf = Cnf::new(variable_names);
+        for clause in clauses {
+            cnf.add_clause(clause);
+        }
+        Ok(cnf)
+    }
+
+    // A "c var" comment per variable, the problem line, then a clause per line
+    pub fn to_dimacs(&self) -> String {
+        let mut result = String::new();
+        for (index, var) in self.variables().iter().enumerate() {
+            writeln!(result, "c var {} {}", index + 1, var).unwrap();
+        }
+        writeln!(result, "p cnf {} {}", self.variables().len(), self.clauses().len()).unwrap();
+        for clause in self.clauses() {
+            for literal in clause {
+                let number = literal.variable() as i64 + 1;
+                write!(result, "{} ", if literal.is_negated() { -number } else { number }).unwrap();
+            }
+            result.push_str("0\n");
+        }
+        result
+    }
+}
+
+impl Expression {
+    // Conjunction of the clauses of a DIMACS file
+    pub fn from_dimacs(text: &str) -> Result<Self, LogicError> {
+        Ok(Cnf::from_dimacs(text)?.to_expression())
+    }
+
+    pub fn to_dimacs(&self, encoding: CnfEncoding) -> Result<String, LogicError> {
+        Ok(self.to_cnf_with(encoding)?.to_dimacs())
+    }
+}
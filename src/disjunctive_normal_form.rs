@@ -0,0 +1,48 @@
+use crate::conjunctive_normal_form::{distribute, flatten_expression, NormalForm};
+use crate::{normalize, Expression, LogicError};
+
+// Space complexity: O(2^m):
+//      Expression tree - O(m)
+//      NNF Expression - O(m)
+//      Call stack - O(m)
+//      DNF expression - O(2^m) - can be exponentially larger than the input
+// Time complexity:  O(2^m)
+//   Expression parsing: O(m)
+//   NNF Conversion: O(m)
+//   DNF conversion: O(2^m)
+//      OR op: 2 recursive calls
+//      AND op: distributing can lead to exponential growth
+//      Flattening: O(m)
+pub fn disjunctive_normal_form(formula: &str) -> String {
+    match try_disjunctive_normal_form(formula) {
+        Ok(value) => value,
+        Err(err) => {
+            eprintln!("{}", err.render(formula));
+            String::new()
+        }
+    }
+}
+
+pub fn try_disjunctive_normal_form(formula: &str) -> Result<String, LogicError> {
+    Ok(Expression::from_formula(formula)?.to_dnf()?.to_rpn())
+}
+
+// Node count past which the DNF conversion gives up
+pub const DEFAULT_DNF_LIMIT: usize = 1 << 20;
+
+impl Expression {
+    // Equivalent DNF built by distributing AND over OR, a right-associative
+    // disjunction of right-associative conjunctions of literals.
+    // Fails with DnfTooLarge when the result grows past DEFAULT_DNF_LIMIT nodes.
+    pub fn to_dnf(&self) -> Result<Expression, LogicError> {
+        self.to_dnf_limited(DEFAULT_DNF_LIMIT)
+    }
+
+    // Same as to_dnf, giving up past `limit` nodes
+    pub fn to_dnf_limited(&self, limit: usize) -> Result<Expression, LogicError> {
+        // First convert to nnf
+        let nnf = normalize(self);
+        let (dnf, _) = distribute(&nnf, NormalForm::Disjunctive, limit)?;
+        Ok(flatten_expression(&dnf))
+    }
+}
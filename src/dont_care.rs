@@ -0,0 +1,97 @@
+use crate::bitslice::block_count;
+use crate::cube::cover_to_expression;
+use crate::espresso::{cube_covers, espresso, intersect_covers};
+use crate::synthesis::right_fold;
+use crate::truth_table::TruthTable;
+use crate::{Assignment, Expression, LogicError, Program, Variable};
+
+// A function whose value does not matter on some inputs, typically because
+// they can never occur: `expression` is only specified where `dont_care` is false.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncompleteFunction {
+    expression: Expression,
+    dont_care: Expression,
+}
+
+impl Expression {
+    pub fn with_dont_care(self, dont_care: Expression) -> IncompleteFunction {
+        IncompleteFunction::new(self, dont_care)
+    }
+}
+
+impl IncompleteFunction {
+    pub fn new(expression: Expression, dont_care: Expression) -> Self {
+        IncompleteFunction { expression, dont_care }
+    }
+
+    // Each assignment is a combination of inputs that never occurs.
+    // Variables it leaves out may take any value: "A=1" alone makes half of
+    // the rows don't cares.
+    pub fn from_assignments(expression: Expression, assignments: &[Assignment]) -> Self {
+        let products = assignments.iter().map(|assignment| {
+            let literals = assignment.iter().map(|(var, value)| {
+                let literal = Expression::var(var);
+                if value { literal } else { !literal }
+            });
+            right_fold(literals.collect(), Expression::And, true)
+        });
+        IncompleteFunction::new(expression, right_fold(products.collect(), Expression::Or, false))
+    }
+
+    pub fn expression(&self) -> &Expression {
+        &self.expression
+    }
+
+    pub fn dont_care(&self) -> &Expression {
+        &self.dont_care
+    }
+
+    // Variables of the expression and of the don't care set, sorted
+    pub fn variables(&self) -> Vec<Variable> {
+        let mut variables: Vec<_> = self.expression.variables().union(&self.dont_care.variables()).cloned().collect();
+        variables.sort();
+        variables
+    }
+
+    // Table of the expression with the don't care rows marked
+    pub fn truth_table(&self) -> Result<TruthTable, LogicError> {
+        let variables = self.variables();
+        let output = Program::compile_with_variables(&self.expression, &variables)?.truth_column()?;
+        let dont_care = Program::compile_with_variables(&self.dont_care, &variables)?.truth_column()?;
+        Ok(TruthTable::from_column(variables, output).with_dont_care(dont_care))
+    }
+
+    // Same value as `other` on every row outside the don't care set
+    // v = variable count of both, m = size of the three expressions
+    // Space complexity: O(m + v)
+    // Time complexity: O(2^v / 64 . m)
+    pub fn is_equivalent(&self, other: &Expression) -> Result<bool, LogicError> {
+        let mut variables: Vec<_> = self.variables().into_iter().chain(other.variables()).collect();
+        variables.sort();
+        variables.dedup();
+
+        let left = Program::compile_with_variables(&self.expression, &variables)?;
+        let right = Program::compile_with_variables(other, &variables)?;
+        let dont_care = Program::compile_with_variables(&self.dont_care, &variables)?;
+        Ok((0..block_count(variables.len())).all(|block| {
+            (left.evaluate_block(block) ^ right.evaluate_block(block)) & !dont_care.evaluate_block(block) == 0
+        }))
+    }
+
+    // Minimal sum of products agreeing with the expression outside the don't care set
+    pub fn to_minimal_dnf(&self) -> Result<Expression, LogicError> {
+        Ok(self.truth_table()?.to_minimal_dnf())
+    }
+
+    // Same as to_minimal_dnf with espresso, for many variables
+    pub fn to_heuristic_dnf(&self) -> Result<Expression, LogicError> {
+        let variables = self.variables();
+        let (on, off) = cube_covers(&self.expression, &variables)?;
+        let (dont_care, care) = cube_covers(&self.dont_care, &variables)?;
+
+        let on = intersect_covers(&on, &care);
+        let off = intersect_covers(&off, &care);
+        let cover = espresso(variables.len(), &on, &dont_care, &off);
+        Ok(cover_to_expression(&cover, &variables))
+    }
+}
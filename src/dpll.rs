@@ -0,0 +1,212 @@
+use crate::cnf::{Cnf, Literal};
+
+// Davis-Putnam-Logemann-Loveland search: assign a literal, simplify, and undo
+// the assignment to try the opposite one when it leads to a conflict.
+// Between decisions, unit clauses force their last literal and pure literals,
+// whose negation is in no open clause, are set to true.
+// The branching literal is the one occurring most in the open clauses.
+// Returns a model, values[i] being the value of variable i, variables left
+// free by the search are false.
+// k = clause count, l = literals per clause, v = variable count
+// Space complexity: O(k . l + v)
+// Time complexity: O(2^v . k . l) in the worst case, far less on most inputs
+pub fn dpll(cnf: &Cnf) -> Option<Vec<bool>> {
+    let mut solver = Solver::new(cnf);
+    if !solver.search() {
+        return None;
+    }
+    Some(solver.values.iter().map(|value| value.unwrap_or(false)).collect())
+}
+
+// Assignment with its trail and unit propagation, shared with the model counter
+pub(crate) struct Solver<'a> {
+    cnf: &'a Cnf,
+    values: Vec<Option<bool>>,
+    // Assigned literals in order, undone from the end when backtracking
+    trail: Vec<Literal>,
+    // Trail entries before this one had their clauses checked
+    propagated: usize,
+    // Clauses holding each literal, by Literal::index
+    occurrences: Vec<Vec<usize>>,
+}
+
+// How the open clauses stand after a scan
+enum Scan {
+    Satisfied,
+    // Pure literals to set
+    Pure(Vec<Literal>),
+    Branch(Literal),
+}
+
+// A branching literal, with the trail length before it was assigned
+struct Decision {
+    length: usize,
+    literal: Literal,
+    // Whether the opposite literal is being tried, the last chance of this decision
+    flipped: bool,
+}
+
+impl<'a> Solver<'a> {
+    pub(crate) fn new(cnf: &'a Cnf) -> Self {
+        let variable_count = cnf.variables().len();
+        let mut occurrences = vec![Vec::new(); 2 * variable_count];
+        for (index, clause) in cnf.clauses().iter().enumerate() {
+            for literal in clause {
+                occurrences[literal.index()].push(index);
+            }
+        }
+        Solver { cnf, values: vec![None; variable_count], trail: Vec::new(), propagated: 0, occurrences }
+    }
+
+    pub(crate) fn value(&self, literal: Literal) -> Option<bool> {
+        self.values[literal.variable()].map(|value| literal.value(value))
+    }
+
+    pub(crate) fn assign(&mut self, literal: Literal) {
+        self.values[literal.variable()] = Some(!literal.is_negated());
+        self.trail.push(literal);
+    }
+
+    // Number of assigned variables, the length to undo to
+    pub(crate) fn trail_length(&self) -> usize {
+        self.trail.len()
+    }
+
+    pub(crate) fn undo(&mut self, length: usize) {
+        for literal in self.trail.drain(length..) {
+            self.values[literal.variable()] = None;
+        }
+        self.propagated = self.propagated.min(length);
+    }
+
+    // Depth-first over the decisions, kept on an explicit stack rather than
+    // the call stack so the depth is only bounded by the variable count
+    fn search(&mut self) -> bool {
+        if !self.assign_units() {
+            return false;
+        }
+
+        let mut decisions: Vec<Decision> = Vec::new();
+        loop {
+            if !self.propagate() {
+                if !self.backtrack(&mut decisions) {
+                    return false;
+                }
+                continue;
+            }
+            match self.scan() {
+                Scan::Satisfied => return true,
+                Scan::Pure(literals) => {
+                    for literal in literals {
+                        self.assign(literal);
+                    }
+                },
+                Scan::Branch(literal) => {
+                    decisions.push(Decision { length: self.trail.len(), literal, flipped: false });
+                    self.assign(literal);
+                },
+            }
+        }
+    }
+
+    // Undoes the decisions whose both literals failed, then tries the
+    // opposite of the last remaining one. Returns false when none is left.
+    fn backtrack(&mut self, decisions: &mut Vec<Decision>) -> bool {
+        while let Some(decision) = decisions.pop() {
+            if decision.flipped {
+                continue;
+            }
+            self.undo(decision.length);
+            decisions.push(Decision { flipped: true, ..decision });
+            self.assign(!decision.literal);
+            return true;
+        }
+        false
+    }
+
+    // Propagation only visits the clauses of falsified literals, so the
+    // clauses that are units or empty from the start are handled here.
+    // Returns false on a conflict.
+    pub(crate) fn assign_units(&mut self) -> bool {
+        for clause in self.cnf.clauses() {
+            match clause[..] {
+                [] => return false,
+                [unit] => match self.value(unit) {
+                    Some(true) => {},
+                    Some(false) => return false,
+                    None => self.assign(unit),
+                },
+                _ => {},
+            }
+        }
+        true
+    }
+
+    // Unit propagation through the clauses of the literals made false since
+    // the last call. Returns false on a conflict.
+    pub(crate) fn propagate(&mut self) -> bool {
+        while self.propagated < self.trail.len() {
+            let falsified = !self.trail[self.propagated];
+            self.propagated += 1;
+
+            for index in 0..self.occurrences[falsified.index()].len() {
+                let clause = &self.cnf.clauses()[self.occurrences[falsified.index()][index]];
+                let mut unassigned = None;
+                let mut open = 0;
+                let mut satisfied = false;
+                for &literal in clause {
+                    match self.value(literal) {
+                        Some(true) => {
+                            satisfied = true;
+                            break;
+                        },
+                        Some(false) => {},
+                        None => {
+                            open += 1;
+                            unassigned = Some(literal);
+                        },
+                    }
+                }
+                match (satisfied, open) {
+                    (true, _) => {},
+                    (false, 0) => return false,
+                    (false, 1) => self.assign(unassigned.unwrap()),
+                    _ => {},
+                }
+            }
+        }
+        true
+    }
+
+    // Looks at every clause not yet satisfied for pure literals, otherwise
+    // picks a literal. Units and conflicts are left to propagate.
+    fn scan(&self) -> Scan {
+        let mut counts = vec![0usize; self.occurrences.len()];
+        let mut open_clauses = false;
+
+        for clause in self.cnf.clauses() {
+            if clause.iter().any(|&literal| self.value(literal) == Some(true)) {
+                continue;
+            }
+            open_clauses = true;
+            for &literal in clause {
+                if self.value(literal).is_none() {
+                    counts[literal.index()] += 1;
+                }
+            }
+        }
+
+        if !open_clauses {
+            return Scan::Satisfied;
+        }
+        let pure: Vec<Literal> = (0..counts.len())
+            .filter(|&index| counts[index] > 0 && counts[index ^ 1] == 0)
+            .map(|index| Literal::new(index >> 1, index & 1 == 1))
+            .collect();
+        if !pure.is_empty() {
+            return Scan::Pure(pure);
+        }
+        let best = (0..counts.len()).max_by_key(|&index| counts[index]).unwrap(); // There is an open clause
+        Scan::Branch(Literal::new(best >> 1, best & 1 == 1))
+    }
+}
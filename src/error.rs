@@ -1,10 +1,108 @@
+use std::fmt;
 
-#[derive(Debug)]
+// Every variant produced while reading a formula carries the byte offset
+// where the problem was detected, so it can be pointed at with `render`.
+#[derive(Debug, Clone, PartialEq)]
 pub enum LogicError {
-    MissingArgument,
-    UnrecognizedSymbol,
-    IncompleteFormula,
+    // An operator found fewer operands than its arity on the stack
+    MissingArgument { position: usize, symbol: char, expected: usize, found: usize },
+    UnrecognizedSymbol { position: usize, symbol: char },
+    // The formula does not reduce to exactly one value
+    IncompleteFormula { position: usize, depth: usize },
+    // An operator the CNF conversion cannot handle, outside negation normal form
     UnexpectedOperatorCNF,
-    UnknownVar,
+    // The distributive CNF conversion grew past `limit` nodes
+    CnfTooLarge { limit: usize },
+    // The DNF counterparts of the two errors above
+    UnexpectedOperatorDNF,
+    DnfTooLarge { limit: usize },
+    // A variable with no matching set
+    UnknownVar { position: usize, symbol: char },
+    UnbalancedParenthesis { position: usize },
+    // A `name=value` pair that could not be read
+    InvalidAssignment { position: usize },
+    // A variable of the formula with no value in the assignment
+    UnassignedVariable { name: String },
+    // More variables than a compiled program can address
+    TooManyVariables { count: usize, max: usize },
+    // A row number past the end of a truth table
+    InvalidRow { row: u64, row_count: u64 },
+    // Line based input such as a markdown table, `line` starts at 1
+    Malformed { line: usize, message: String },
 }
 
+impl LogicError {
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            LogicError::MissingArgument { position, .. } |
+            LogicError::UnrecognizedSymbol { position, .. } |
+            LogicError::IncompleteFormula { position, .. } |
+            LogicError::UnknownVar { position, .. } |
+            LogicError::UnbalancedParenthesis { position } |
+            LogicError::InvalidAssignment { position } => Some(*position),
+            LogicError::UnexpectedOperatorCNF |
+            LogicError::CnfTooLarge { .. } |
+            LogicError::UnexpectedOperatorDNF |
+            LogicError::DnfTooLarge { .. } |
+            LogicError::UnassignedVariable { .. } |
+            LogicError::TooManyVariables { .. } |
+            LogicError::InvalidRow { .. } |
+            LogicError::Malformed { .. } => None,
+        }
+    }
+
+    // Human readable diagnostic with a caret under the offending character:
+    //
+    //   error: '|' expects 2 operands, found 1
+    //     1|0
+    //      ^
+    pub fn render(&self, formula: &str) -> String {
+        let mut result = format!("error: {}", self.message());
+
+        if let Some(position) = self.position() {
+            // Column in characters, so multi-byte symbols such as '⇒' stay aligned
+            let column = formula
+                .char_indices()
+                .take_while(|&(offset, _)| offset < position)
+                .count();
+            result.push_str(&format!("\n  {}\n  {}^", formula, " ".repeat(column)));
+        }
+
+        result
+    }
+
+    fn message(&self) -> String {
+        match self {
+            LogicError::MissingArgument { symbol, expected, found, .. } => {
+                let plural = if *expected == 1 { "" } else { "s" };
+                format!("'{symbol}' expects {expected} operand{plural}, found {found}")
+            },
+            LogicError::UnrecognizedSymbol { symbol, .. } => format!("unrecognized symbol '{symbol}'"),
+            LogicError::IncompleteFormula { depth, .. } => {
+                format!("incomplete formula: expected a single result, found {depth}")
+            },
+            LogicError::UnexpectedOperatorCNF => "unexpected operator in CNF conversion".to_string(),
+            LogicError::CnfTooLarge { limit } => format!("the CNF exceeds {limit} nodes"),
+            LogicError::UnexpectedOperatorDNF => "unexpected operator in DNF conversion".to_string(),
+            LogicError::DnfTooLarge { limit } => format!("the DNF exceeds {limit} nodes"),
+            LogicError::UnknownVar { symbol, .. } => format!("unknown variable '{symbol}'"),
+            LogicError::UnbalancedParenthesis { .. } => "unbalanced parenthesis".to_string(),
+            LogicError::InvalidAssignment { .. } => "invalid assignment: expected `name=0` or `name=1`".to_string(),
+            LogicError::UnassignedVariable { name } => format!("variable '{name}' has no assigned value"),
+            LogicError::TooManyVariables { count, max } => format!("{count} variables, at most {max} are supported"),
+            LogicError::InvalidRow { row, row_count } => format!("row {row} is out of range, the table has {row_count} rows"),
+            LogicError::Malformed { line, message } => format!("line {line}: {message}"),
+        }
+    }
+}
+
+impl fmt::Display for LogicError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.position() {
+            Some(position) => write!(f, "{} (at offset {})", self.message(), position),
+            None => write!(f, "{}", self.message()),
+        }
+    }
+}
+
+impl std::error::Error for LogicError {}
@@ -0,0 +1,315 @@
+use crate::cube::{cover_to_expression, full_mask, Cube};
+use crate::program::MAX_VARIABLES;
+use crate::{Expression, LogicError, Variable};
+
+// Heuristic two-level minimization in the style of Espresso.
+// Unlike Quine-McCluskey nothing is enumerated row by row: the function is
+// kept as a cover, a list of cubes whose union is the set of true rows, next
+// to a cover of the false rows. Cubes are grown as long as they avoid the
+// false rows (expand), cubes covered by the others are dropped (irredundant),
+// then each cube is shrunk back to what only it covers (reduce) so the next
+// expansion can move it somewhere better. The loop stops when a pass does not
+// lower the cost. The result is irredundant and made of prime implicants but
+// not necessarily minimum.
+// v = variable count, k = size of the cover
+// Time complexity: polynomial in k and v per pass, except the covering checks
+// which are exponential in the worst case
+pub fn heuristic_sum_of_products(formula: &str) -> String {
+    match try_heuristic_sum_of_products(formula) {
+        Ok(value) => value,
+        Err(err) => {
+            eprintln!("{}", err.render(formula));
+            String::new()
+        }
+    }
+}
+
+pub fn try_heuristic_sum_of_products(formula: &str) -> Result<String, LogicError> {
+    let expression = Expression::from_formula(formula)?;
+    Ok(expression.to_heuristic_dnf()?.to_rpn())
+}
+
+impl Expression {
+    // Small equivalent sum of products, without building the truth table, so
+    // it works for formulas with up to 64 variables
+    pub fn to_heuristic_dnf(&self) -> Result<Expression, LogicError> {
+        let variables = self.sorted_variables();
+        let (on, off) = cube_covers(self, &variables)?;
+        let cover = espresso(variables.len(), &on, &[], &off);
+        Ok(cover_to_expression(&cover, &variables))
+    }
+}
+
+// Covers of the rows where the expression is true and false, built bottom-up:
+// the true rows of A & B are the pairwise intersections of the true cubes of
+// A and B, its false rows the union of the false cubes, and so on.
+// Contained cubes are dropped along the way but the covers can still grow
+// exponentially with the nesting of the formula.
+pub fn cube_covers(expr: &Expression, variables: &[Variable]) -> Result<(Vec<Cube>, Vec<Cube>), LogicError> {
+    if variables.len() > MAX_VARIABLES {
+        return Err(LogicError::TooManyVariables { count: variables.len(), max: MAX_VARIABLES });
+    }
+
+    let n = variables.len();
+    let covers = match expr {
+        Expression::Val(true) => (vec![Cube::UNIVERSE], Vec::new()),
+        Expression::Val(false) => (Vec::new(), vec![Cube::UNIVERSE]),
+        Expression::Var(v) => {
+            let index = variables
+                .iter()
+                .position(|known| known == v)
+                .ok_or_else(|| LogicError::UnassignedVariable { name: v.to_string() })?;
+            let bit = 1 << (n - 1 - index);
+            (vec![Cube::new(bit, bit)], vec![Cube::new(bit, 0)])
+        },
+        Expression::Neg(child) => {
+            let (on, off) = cube_covers(child, variables)?;
+            (off, on)
+        },
+        Expression::And(left, right) |
+        Expression::Or(left, right) |
+        Expression::Xor(left, right) |
+        Expression::Implication(left, right) |
+        Expression::Equivalence(left, right) => {
+            let (left_on, left_off) = cube_covers(left, variables)?;
+            let (right_on, right_off) = cube_covers(right, variables)?;
+            let both = |a: &[Cube], b: &[Cube]| intersect_covers(a, b);
+            let either = |a: Vec<Cube>, b: Vec<Cube>| remove_contained([a, b].concat());
+
+            match expr {
+                Expression::And(_, _) => (both(&left_on, &right_on), either(left_off, right_off)),
+                Expression::Or(_, _) => (either(left_on, right_on), both(&left_off, &right_off)),
+                Expression::Implication(_, _) => (either(left_off, right_on), both(&left_on, &right_off)),
+                Expression::Xor(_, _) => (
+                    either(both(&left_on, &right_off), both(&left_off, &right_on)),
+                    either(both(&left_on, &right_on), both(&left_off, &right_off)),
+                ),
+                _ => (
+                    either(both(&left_on, &right_on), both(&left_off, &right_off)),
+                    either(both(&left_on, &right_off), both(&left_off, &right_on)),
+                ),
+            }
+        },
+    };
+    Ok(covers)
+}
+
+// Minimizes the cover `on`, free to cover any row of `dont_care`, never
+// covering a row of `off`. The three sets must partition the rows.
+pub fn espresso(variable_count: usize, on: &[Cube], dont_care: &[Cube], off: &[Cube]) -> Vec<Cube> {
+    let mut cover = remove_contained(on.to_vec());
+    if cover.is_empty() {
+        return cover;
+    }
+
+    cover = expand(cover, off);
+    cover = irredundant(variable_count, cover, dont_care);
+    loop {
+        let cost = cost(&cover);
+        let mut candidate = reduce(variable_count, cover.clone(), dont_care);
+        candidate = expand(candidate, off);
+        candidate = irredundant(variable_count, candidate, dont_care);
+        if self::cost(&candidate) >= cost {
+            break;
+        }
+        cover = candidate;
+    }
+
+    cover.sort_by_key(|cube| (cube.literal_count(), std::cmp::Reverse(cube.mask), cube.value));
+    cover
+}
+
+// Cubes first, then literals
+fn cost(cover: &[Cube]) -> (usize, u32) {
+    (cover.len(), cover.iter().map(Cube::literal_count).sum())
+}
+
+// Grows each cube into a prime implicant by dropping literals while it stays
+// clear of the off-set, then removes the cubes it swallowed.
+// Literals are dropped in order of how many other cubes disagree on them:
+// freeing those makes the cube reach towards the rest of the cover.
+fn expand(mut cover: Vec<Cube>, off: &[Cube]) -> Vec<Cube> {
+    // Small cubes have the most room to grow, expand the large ones first
+    cover.sort_by_key(Cube::literal_count);
+
+    let mut expanded: Vec<Cube> = Vec::new();
+    for &original in &cover {
+        let mut cube = original;
+        if expanded.iter().any(|prime| prime.contains(&cube)) {
+            continue;
+        }
+
+        let mut literals: Vec<u64> = bits(cube.mask).collect();
+        literals.sort_by_key(|&bit| {
+            let disagreeing = cover.iter().filter(|other| other.mask & bit == 0 || other.value & bit != cube.value & bit);
+            std::cmp::Reverse(disagreeing.count())
+        });
+        for bit in literals {
+            let raised = Cube::new(cube.mask & !bit, cube.value);
+            if off.iter().all(|blocked| raised.intersect(blocked).is_none()) {
+                cube = raised;
+            }
+        }
+
+        expanded.retain(|other| !cube.contains(other));
+        expanded.push(cube);
+    }
+    expanded
+}
+
+// Drops cubes covered by the rest of the cover and the don't cares, smallest first
+fn irredundant(variable_count: usize, mut cover: Vec<Cube>, dont_care: &[Cube]) -> Vec<Cube> {
+    cover.sort_by_key(|cube| std::cmp::Reverse(cube.literal_count()));
+
+    let mut index = 0;
+    while index < cover.len() {
+        let cube = cover[index];
+        let others: Vec<Cube> = cover.iter().enumerate().filter(|&(i, _)| i != index).map(|(_, c)| *c).collect();
+        if covers(variable_count, &[others, dont_care.to_vec()].concat(), &cube) {
+            cover.remove(index);
+        } else {
+            index += 1;
+        }
+    }
+    cover
+}
+
+// Shrinks each cube to the rows no other cube covers: for every free
+// variable, if one half of the cube is covered elsewhere keep the other half.
+fn reduce(variable_count: usize, mut cover: Vec<Cube>, dont_care: &[Cube]) -> Vec<Cube> {
+    cover.sort_by_key(Cube::literal_count);
+
+    let mut index = 0;
+    while index < cover.len() {
+        let others: Vec<Cube> = cover.iter().enumerate().filter(|&(i, _)| i != index).map(|(_, c)| *c).collect();
+        let others = [others, dont_care.to_vec()].concat();
+
+        let mut cube = cover[index];
+        if covers(variable_count, &others, &cube) {
+            cover.remove(index);
+            continue;
+        }
+        for bit in bits(full_mask(variable_count) & !cube.mask) {
+            let high = Cube::new(cube.mask | bit, cube.value | bit);
+            let low = Cube::new(cube.mask | bit, cube.value);
+            if covers(variable_count, &others, &high) {
+                cube = low;
+            } else if covers(variable_count, &others, &low) {
+                cube = high;
+            }
+        }
+        cover[index] = cube;
+        index += 1;
+    }
+    cover
+}
+
+// Every row of `cube` is covered by `cover`: the cofactor of the cover with
+// respect to the cube is a tautology
+pub fn covers(variable_count: usize, cover: &[Cube], cube: &Cube) -> bool {
+    let cofactor: Vec<Cube> = cover.iter().filter_map(|c| c.cofactor(cube)).collect();
+    is_tautology(variable_count, &cofactor)
+}
+
+// The cover contains every row.
+// Splits on the most binate variable, the one appearing both plain and
+// negated in the most cubes. A cover without such a variable is unate and is
+// a tautology only if it holds the universe cube.
+pub fn is_tautology(variable_count: usize, cover: &[Cube]) -> bool {
+    if cover.iter().any(|cube| cube.mask == 0) {
+        return true;
+    }
+    let Some(bit) = most_binate(variable_count, cover) else {
+        return false;
+    };
+
+    [Cube::new(bit, bit), Cube::new(bit, 0)].iter().all(|half| {
+        let cofactor: Vec<Cube> = cover.iter().filter_map(|c| c.cofactor(half)).collect();
+        is_tautology(variable_count, &cofactor)
+    })
+}
+
+// Cover of the rows `cover` misses
+// Same splitting as is_tautology: the complement of x.F1 + !x.F0 is
+// x.!F1 + !x.!F0, and the complement of a single cube follows De Morgan.
+pub fn complement(variable_count: usize, cover: &[Cube]) -> Vec<Cube> {
+    if cover.is_empty() {
+        return vec![Cube::UNIVERSE];
+    }
+    if cover.iter().any(|cube| cube.mask == 0) {
+        return Vec::new();
+    }
+    if let [cube] = cover {
+        return bits(cube.mask).map(|bit| Cube::new(bit, !cube.value)).collect();
+    }
+
+    // Unate covers still have to be split, on their most used variable
+    let bit = most_binate(variable_count, cover).unwrap_or_else(|| {
+        let n = variable_count;
+        let uses = |bit: u64| cover.iter().filter(|cube| cube.mask & bit != 0).count();
+        (0..n).map(|i| 1 << i).max_by_key(|&bit| uses(bit)).unwrap() // A cube without the universe has a variable
+    });
+
+    let halves = [Cube::new(bit, bit), Cube::new(bit, 0)];
+    let [high, low] = halves.map(|half| {
+        let cofactor: Vec<Cube> = cover.iter().filter_map(|c| c.cofactor(&half)).collect();
+        complement(variable_count, &cofactor)
+    });
+
+    // A cube in both halves does not depend on the variable
+    let mut result = Vec::new();
+    for cube in &high {
+        if low.contains(cube) {
+            result.push(*cube);
+        } else {
+            result.push(Cube::new(cube.mask | bit, cube.value | bit));
+        }
+    }
+    for cube in low.iter().filter(|cube| !high.contains(cube)) {
+        result.push(Cube::new(cube.mask | bit, cube.value));
+    }
+    remove_contained(result)
+}
+
+// Drops duplicates and cubes contained in another cube of the cover
+pub fn remove_contained(mut cover: Vec<Cube>) -> Vec<Cube> {
+    cover.sort_by_key(Cube::literal_count);
+    let mut kept: Vec<Cube> = Vec::new();
+    for cube in cover {
+        if !kept.iter().any(|larger| larger.contains(&cube)) {
+            kept.push(cube);
+        }
+    }
+    kept
+}
+
+// Rows in both covers
+pub fn intersect_covers(a: &[Cube], b: &[Cube]) -> Vec<Cube> {
+    let products = a.iter().flat_map(|x| b.iter().filter_map(move |y| x.intersect(y)));
+    remove_contained(products.collect())
+}
+
+fn most_binate(variable_count: usize, cover: &[Cube]) -> Option<u64> {
+    (0..variable_count)
+        .map(|i| 1u64 << i)
+        .filter_map(|bit| {
+            let plain = cover.iter().filter(|cube| cube.mask & cube.value & bit != 0).count();
+            let negated = cover.iter().filter(|cube| cube.mask & !cube.value & bit != 0).count();
+            (plain > 0 && negated > 0).then_some((plain + negated, bit))
+        })
+        .max()
+        .map(|(_, bit)| bit)
+}
+
+// The single-bit masks of the set bits of `mask`, lowest first
+fn bits(mask: u64) -> impl Iterator<Item = u64> {
+    let mut rest = mask;
+    std::iter::from_fn(move || {
+        if rest == 0 {
+            return None;
+        }
+        let bit = rest & rest.wrapping_neg();
+        rest &= !bit;
+        Some(bit)
+    })
+}
@@ -1,4 +1,4 @@
-use crate::{pop_from_stack, LogicError};
+use crate::{pop_operand, pop_operands, pop_result, Assignment, Expression, LogicError};
 
 // Space complexity: O(n) - linear with formula length
 // Time complexity: O(n) - linear with formula length
@@ -6,16 +6,16 @@ pub fn eval_formula(formula: &str) -> bool {
     match try_eval_formula(formula) {
         Ok(value) => value,
         Err(err) => {
-            eprintln!("Error occurred while evaluating: {err:?}");
+            eprintln!("{}", err.render(formula));
             false
         }
     }
 }
 
-fn try_eval_formula(formula: &str) -> Result<bool, LogicError> {
+pub fn try_eval_formula(formula: &str) -> Result<bool, LogicError> {
     let mut stack: Vec<bool> = Vec::with_capacity(formula.len());
 
-    for character in formula.chars() {
+    for (position, character) in formula.char_indices() {
         match character {
             // Constants
             '1' => stack.push(true),
@@ -23,47 +23,54 @@ fn try_eval_formula(formula: &str) -> Result<bool, LogicError> {
 
             // Negation
             '!' => {
-                let val = pop_from_stack(&mut stack)?;
+                let val = pop_operand(&mut stack, position, character)?;
                 stack.push(!val);
             },
 
             // Binary operators
             '&' => {
-                let right = pop_from_stack(&mut stack)?;
-                let left = pop_from_stack(&mut stack)?;
+                let (left, right) = pop_operands(&mut stack, position, character)?;
                 stack.push(left && right);
             },
             '|' => {
-                let right = pop_from_stack(&mut stack)?;
-                let left = pop_from_stack(&mut stack)?;
+                let (left, right) = pop_operands(&mut stack, position, character)?;
                 stack.push(left || right);
             },
             '^' => {
-                let right = pop_from_stack(&mut stack)?;
-                let left = pop_from_stack(&mut stack)?;
+                let (left, right) = pop_operands(&mut stack, position, character)?;
                 stack.push(left != right);
             },
             '>' => {
-                let right = pop_from_stack(&mut stack)?;
-                let left = pop_from_stack(&mut stack)?;
+                let (left, right) = pop_operands(&mut stack, position, character)?;
                 stack.push(!left || right);
             },
             '=' => {
-                let right = pop_from_stack(&mut stack)?;
-                let left = pop_from_stack(&mut stack)?;
+                let (left, right) = pop_operands(&mut stack, position, character)?;
                 stack.push(left == right);
             },
 
             // Unrecognized character
-            _ => return Err(LogicError::UnrecognizedSymbol),
+            _ => return Err(LogicError::UnrecognizedSymbol { position, symbol: character }),
         }
     }
 
-    if stack.len() != 1 {
-        return Err(LogicError::IncompleteFormula);
-    }
+    pop_result(&mut stack, formula)
+}
 
-    // Safe because we just checked stack.len() == 1
-    Ok(stack.pop().unwrap()) 
+// Like eval_formula, but variables take their value from `assignment`
+// m = formula length
+// Space complexity: O(m) - expression tree
+// Time complexity: O(m) - parsing and a single evaluation pass
+pub fn eval_formula_with(formula: &str, assignment: &Assignment) -> bool {
+    match try_eval_formula_with(formula, assignment) {
+        Ok(value) => value,
+        Err(err) => {
+            eprintln!("{}", err.render(formula));
+            false
+        }
+    }
 }
 
+pub fn try_eval_formula_with(formula: &str, assignment: &Assignment) -> Result<bool, LogicError> {
+    Expression::from_formula(formula)?.evaluate(assignment)
+}
@@ -1,11 +1,13 @@
-use std::{collections::HashSet, fmt};
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+use std::fmt;
 
-use crate::{pop_from_stack, LogicError};
+use crate::variable::{is_identifier_start, read_identifier};
+use crate::{pop_operand, pop_operands, pop_result, Assignment, LogicError, Variable};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Expression {
     Val(bool),
-    Var(char),
+    Var(Variable),
     Neg(Box<Expression>),
     And(Box<Expression>, Box<Expression>),
     Or(Box<Expression>, Box<Expression>),
@@ -18,66 +20,84 @@ impl Expression {
     pub fn from_formula(formula: &str) -> Result<Self, LogicError> {
         let mut stack: Vec<Expression> = Vec::with_capacity(formula.len());
 
-        for character in formula.chars() {
+        let mut chars = formula.char_indices().peekable();
+        while let Some((position, character)) = chars.next() {
             match character {
                 // Constants
                 '1' => stack.push(Expression::Val(true)),
                 '0' => stack.push(Expression::Val(false)),
 
                 // Variables
-                'A'..='Z' => stack.push(Expression::Var(character)),
+                'A'..='Z' => stack.push(Expression::var(character)),
+                c if is_identifier_start(c) => {
+                    let (variable, end) = read_identifier(formula, position);
+                    while chars.next_if(|&(offset, _)| offset < end).is_some() {}
+                    stack.push(Expression::Var(variable));
+                },
+
+                // Whitespace separates multi-character variables
+                c if c.is_whitespace() => {},
 
                 // Negation
                 '!' => {
-                    let operand = pop_from_stack(&mut stack)?;
+                    let operand = pop_operand(&mut stack, position, character)?;
                     stack.push(!operand);
                 },
 
                 // Binary operators - using the helper function
-                '&' => handle_binary_op(&mut stack, Expression::And)?,
-                '|' => handle_binary_op(&mut stack, Expression::Or)?,
-                '^' => handle_binary_op(&mut stack, Expression::Xor)?,
-                '>' => handle_binary_op(&mut stack, Expression::Implication)?,
-                '=' => handle_binary_op(&mut stack, Expression::Equivalence)?,
+                '&' => handle_binary_op(&mut stack, position, character, Expression::And)?,
+                '|' => handle_binary_op(&mut stack, position, character, Expression::Or)?,
+                '^' => handle_binary_op(&mut stack, position, character, Expression::Xor)?,
+                '>' => handle_binary_op(&mut stack, position, character, Expression::Implication)?,
+                '=' => handle_binary_op(&mut stack, position, character, Expression::Equivalence)?,
 
                 // Unrecognized character
-                _ => return Err(LogicError::UnrecognizedSymbol),
+                _ => return Err(LogicError::UnrecognizedSymbol { position, symbol: character }),
             }
         }
 
         // Make sure we have exactly one element in the stack (the full expression)
-        if stack.len() != 1 {
-            return Err(LogicError::IncompleteFormula);
-        }
-
-        let root_expr = stack.pop().unwrap(); // Safe because we just checked stack.len() == 1
-        Ok(root_expr)
+        pop_result(&mut stack, formula)
     }
 
-    pub fn evaluate(&self, values: &HashSet<char>) -> bool {
+    // Fails if any variable of the expression has no value in the assignment,
+    // even one whose value would not change the result
+    pub fn evaluate(&self, assignment: &Assignment) -> Result<bool, LogicError> {
         match self {
-            Expression::Val(val) => *val,
-            Expression::Var(c) => values.contains(c),
-            Expression::Neg(child) => !child.evaluate(values),
-            Expression::And(left, right) => left.evaluate(values) && right.evaluate(values),
-            Expression::Or(left, right) => left.evaluate(values) || right.evaluate(values),
-            Expression::Xor(left, right) => left.evaluate(values) != right.evaluate(values),
-            Expression::Implication(left, right) => !left.evaluate(values) || right.evaluate(values),
-            Expression::Equivalence(left, right) => left.evaluate(values) == right.evaluate(values),
+            Expression::Val(val) => Ok(*val),
+            Expression::Var(v) => assignment
+                .get(v)
+                .ok_or_else(|| LogicError::UnassignedVariable { name: v.to_string() }),
+            Expression::Neg(child) => Ok(!child.evaluate(assignment)?),
+            Expression::And(left, right) |
+            Expression::Or(left, right) |
+            Expression::Xor(left, right) |
+            Expression::Implication(left, right) |
+            Expression::Equivalence(left, right) => {
+                let left = left.evaluate(assignment)?;
+                let right = right.evaluate(assignment)?;
+                Ok(match self {
+                    Expression::And(_, _) => left && right,
+                    Expression::Or(_, _) => left || right,
+                    Expression::Xor(_, _) => left != right,
+                    Expression::Implication(_, _) => !left || right,
+                    _ => left == right,
+                })
+            },
         }
     }
 
-    pub fn to_unicode(&self) -> char {
+    pub fn to_unicode(&self) -> String {
         match self {
-            Expression::Val(true) => '1',
-            Expression::Val(false) => '0',
-            Expression::Var(c) => *c,
-            Expression::Neg(_) => '!',
-            Expression::And(_, _) => '&',
-            Expression::Or(_, _) => '|',
-            Expression::Xor(_, _) => '^',
-            Expression::Implication(_, _) => '>',
-            Expression::Equivalence(_, _) => '=',
+            Expression::Val(true) => "1".to_string(),
+            Expression::Val(false) => "0".to_string(),
+            Expression::Var(v) => v.to_string(),
+            Expression::Neg(_) => "!".to_string(),
+            Expression::And(_, _) => "&".to_string(),
+            Expression::Or(_, _) => "|".to_string(),
+            Expression::Xor(_, _) => "^".to_string(),
+            Expression::Implication(_, _) => ">".to_string(),
+            Expression::Equivalence(_, _) => "=".to_string(),
         }
     }
 
@@ -86,8 +106,8 @@ impl Expression {
     }
 
     // Construction Functions
-    pub fn var(c: char) -> Expression {
-        Expression::Var(c)
+    pub fn var(v: impl Into<Variable>) -> Expression {
+        Expression::Var(v.into())
     }
 
     pub fn val(b: bool) -> Expression {
@@ -118,37 +138,52 @@ impl Expression {
         Expression::Equivalence(Box::new(a.into()), Box::new(b.into()))
     }
 
+    // Tokens are only separated by spaces when a multi-character variable
+    // would otherwise run into its neighbours.
     pub fn to_rpn(&self) -> String {
+        let mut tokens = Vec::new();
+        self.collect_rpn_tokens(&mut tokens);
+
+        let separator = if self.variables().iter().all(Variable::is_letter) { "" } else { " " };
+        tokens.join(separator)
+    }
+
+    fn collect_rpn_tokens(&self, tokens: &mut Vec<String>) {
         match self {
-            Expression::Val(_) | Expression::Var(_) => self.to_unicode().to_string(),
+            Expression::Val(_) | Expression::Var(_) => tokens.push(self.to_unicode()),
             Expression::Neg(child) => {
-                let mut result = child.to_rpn();
-                result.push(Expression::Neg(Box::new(Expression::Val(false))).to_unicode());
-                result
+                child.collect_rpn_tokens(tokens);
+                tokens.push(self.to_unicode());
             },
             Expression::And(left, right) |
             Expression::Or(left, right) |
             Expression::Xor(left, right) |
             Expression::Implication(left, right) |
             Expression::Equivalence(left, right) => {
-                let mut result = left.to_rpn();
-                result.push_str(&right.to_rpn());
-                result.push(self.to_unicode());
-                result
+                left.collect_rpn_tokens(tokens);
+                right.collect_rpn_tokens(tokens);
+                tokens.push(self.to_unicode());
             }
         }
     }
 
-    pub fn variables(&self) -> HashSet<char> {
+    pub fn variables(&self) -> HashSet<Variable> {
         let mut vars = HashSet::new();
         self.collect_variables(&mut vars);
-        return vars;
+        vars
     }
 
-    fn collect_variables(&self, vars: &mut HashSet<char>) {
+    // Variables in their sorted order, the column order of truth tables
+    pub fn sorted_variables(&self) -> Vec<Variable> {
+        let mut vars: Vec<Variable> = self.variables().into_iter().collect();
+        vars.sort();
+        vars
+    }
+
+    fn collect_variables(&self, vars: &mut HashSet<Variable>) {
         match self {
-            Expression::Var(c) => {
-                vars.insert(*c);
+            Expression::Var(v) => {
+                vars.insert(v.clone());
             },
             Expression::And(left, right) |
             Expression::Or(left, right) |
@@ -194,14 +229,17 @@ impl Expression {
                 let right_width = right.width();
                 left_width + right_width
             }
+            Expression::Neg(child) if child.is_variable() => child.width(),
             Expression::Neg(child) => {
                 child.height() // This is the same as before, using height for width
             }
-            Expression::Val(_) | Expression::Var(_) => 1,
+            Expression::Val(_) => 1,
+            // Names take as many columns as they have characters
+            Expression::Var(v) => v.to_string().chars().count(),
         }
     }
 
-    fn draw_tree(&self, buffer: &mut Vec<Vec<char>>, row: usize, col: usize) {
+    fn draw_tree(&self, buffer: &mut [Vec<char>], row: usize, col: usize) {
         match self {
             Expression::And(left, right) |
             Expression::Or(left, right) |
@@ -209,7 +247,7 @@ impl Expression {
             Expression::Implication(left, right) |
             Expression::Equivalence(left, right) => {
                 // Place operator
-                buffer[row][col] = self.to_unicode();
+                draw_label(&mut buffer[row], col, &self.to_unicode());
 
                 // Calculate positions for children
                 let left_width = left.width();
@@ -230,14 +268,14 @@ impl Expression {
             },
             Expression::Neg(child) => {
                 // Place operator
-                buffer[row][col] = self.to_unicode();
+                draw_label(&mut buffer[row], col, &self.to_unicode());
                 buffer[row + 1][col] = '|';
 
                 // Draw the single child
                 child.draw_tree(buffer, row + 2, col);
             },
             Expression::Val(_) | Expression::Var(_) => {
-                buffer[row][col] = self.to_unicode();
+                draw_label(&mut buffer[row], col, &self.to_unicode());
             }
         }
     }
@@ -256,9 +294,7 @@ impl Expression {
             let last_non_space = row.iter().rposition(|&c| c != ' ').unwrap_or(0);
 
             // Only append up to the last non-space character
-            for i in 0..=last_non_space {
-                result.push(row[i]);
-            }
+            result.extend(&row[..=last_non_space]);
             result.push('\n');
         }
 
@@ -266,6 +302,62 @@ impl Expression {
     }
 }
 
+// Shape of a node with its children replaced by their ids in NodeIds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum NodeKey<'a> {
+    Val(bool),
+    Var(&'a Variable),
+    Neg(usize),
+    And(usize, usize),
+    Or(usize, usize),
+    Xor(usize, usize),
+    Implication(usize, usize),
+    Equivalence(usize, usize),
+}
+
+impl<'a> NodeKey<'a> {
+    // Key of `expr`, its children numbered by `child`, left before right
+    pub(crate) fn new(expr: &'a Expression, mut child: impl FnMut(&'a Expression) -> usize) -> Self {
+        match expr {
+            Expression::Val(value) => NodeKey::Val(*value),
+            Expression::Var(v) => NodeKey::Var(v),
+            Expression::Neg(inner) => NodeKey::Neg(child(inner)),
+            Expression::And(left, right) => NodeKey::And(child(left), child(right)),
+            Expression::Or(left, right) => NodeKey::Or(child(left), child(right)),
+            Expression::Xor(left, right) => NodeKey::Xor(child(left), child(right)),
+            Expression::Implication(left, right) => NodeKey::Implication(child(left), child(right)),
+            Expression::Equivalence(left, right) => NodeKey::Equivalence(child(left), child(right)),
+        }
+    }
+}
+
+// Numbers the distinct subtrees met in a post-order walk, equal subtrees
+// sharing an id. A node is found from the ids of its children, so numbering
+// m nodes takes O(m) where hashing each subtree in full could take O(m^2).
+#[derive(Debug, Default)]
+pub(crate) struct NodeIds<'a> {
+    ids: HashMap<NodeKey<'a>, usize>,
+}
+
+impl<'a> NodeIds<'a> {
+    // Id of the node and whether it is the first one of that shape
+    pub(crate) fn insert(&mut self, key: NodeKey<'a>) -> (usize, bool) {
+        let next = self.ids.len();
+        match self.ids.entry(key) {
+            Entry::Occupied(entry) => (*entry.get(), false),
+            Entry::Vacant(entry) => (*entry.insert(next), true),
+        }
+    }
+}
+
+// Writes `label` centered on `col`, clipped to the row
+fn draw_label(row: &mut [char], col: usize, label: &str) {
+    let start = col.saturating_sub(label.chars().count() / 2);
+    for (cell, c) in row.iter_mut().skip(start).zip(label.chars()) {
+        *cell = c;
+    }
+}
+
 impl fmt::Display for Expression {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.visualize_tree())
@@ -273,9 +365,8 @@ impl fmt::Display for Expression {
 }
 
 pub fn build_and_print_expression(formula: &str) {
-    match Expression::from_formula(formula) {
-        Ok(expr) => println!("{}", expr),
-        Err(_) => {},
+    if let Ok(expr) = Expression::from_formula(formula) {
+        println!("{}", expr);
     }
 }
 
@@ -304,11 +395,12 @@ impl std::ops::Not for Expression {
 }
 
 fn handle_binary_op(
-    stack: &mut Vec<Expression>, 
+    stack: &mut Vec<Expression>,
+    position: usize,
+    symbol: char,
     constructor: fn(Box<Expression>, Box<Expression>) -> Expression
 ) -> Result<(), LogicError> {
-    let right = pop_from_stack(stack)?;
-    let left = pop_from_stack(stack)?;
+    let (left, right) = pop_operands(stack, position, symbol)?;
     stack.push(constructor(Box::new(left), Box::new(right)));
     Ok(())
 }
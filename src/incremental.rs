@@ -0,0 +1,163 @@
+use crate::{gray_code, Expression, LogicError, Variable};
+
+// Largest table walked in Gray order, the width of `gray_code`
+pub const MAX_GRAY_VARIABLES: usize = 32;
+
+#[derive(Debug, Clone, Copy)]
+enum Node {
+    Const(bool),
+    Var(usize),
+    Neg(usize),
+    And(usize, usize),
+    Or(usize, usize),
+    Xor(usize, usize),
+    Implication(usize, usize),
+    Equivalence(usize, usize),
+}
+
+// Keeps the value of every subtree of an expression for the current row, so
+// flipping one variable only recomputes the paths from its leaves to the root,
+// stopping as soon as a subtree keeps its previous value.
+//
+// Rows follow the truth table convention of Program: with n variables,
+// variable i is bit n - 1 - i of the row.
+#[derive(Debug, Clone)]
+pub struct IncrementalEvaluator {
+    variables: Vec<Variable>,
+    // Post-order: children come before their parent, the root is last
+    nodes: Vec<Node>,
+    parents: Vec<Option<usize>>,
+    values: Vec<bool>,
+    // Leaf nodes of each variable
+    leaves: Vec<Vec<usize>>,
+    row: u64,
+}
+
+impl IncrementalEvaluator {
+    // Evaluates the whole tree once, on row 0 where every variable is false
+    pub fn new(expr: &Expression) -> Result<Self, LogicError> {
+        let variables = expr.sorted_variables();
+        if variables.len() > MAX_GRAY_VARIABLES {
+            return Err(LogicError::TooManyVariables { count: variables.len(), max: MAX_GRAY_VARIABLES });
+        }
+
+        let mut evaluator = IncrementalEvaluator {
+            leaves: vec![Vec::new(); variables.len()],
+            variables,
+            nodes: Vec::new(),
+            parents: Vec::new(),
+            values: Vec::new(),
+            row: 0,
+        };
+        evaluator.flatten(expr);
+        for index in 0..evaluator.nodes.len() {
+            let value = evaluator.compute(index);
+            evaluator.values[index] = value;
+        }
+        Ok(evaluator)
+    }
+
+    pub fn variables(&self) -> &[Variable] {
+        &self.variables
+    }
+
+    pub fn row(&self) -> u64 {
+        self.row
+    }
+
+    // Value of the whole expression on the current row
+    pub fn value(&self) -> bool {
+        *self.values.last().unwrap() // A tree has at least one node
+    }
+
+    // Negates the variable at `index` of `variables` and returns the new value
+    // l = leaves of the variable, h = height of the tree
+    // Time complexity: O(l . h)
+    pub fn flip(&mut self, index: usize) -> bool {
+        self.row ^= 1 << (self.variables.len() - 1 - index);
+
+        for leaf_position in 0..self.leaves[index].len() {
+            let leaf = self.leaves[index][leaf_position];
+            self.values[leaf] = !self.values[leaf];
+
+            let mut node = self.parents[leaf];
+            while let Some(current) = node {
+                let value = self.compute(current);
+                if value == self.values[current] {
+                    break;
+                }
+                self.values[current] = value;
+                node = self.parents[current];
+            }
+        }
+
+        self.value()
+    }
+
+    // Every row of the truth table in Gray order, as (row, value) pairs.
+    // Successive rows differ by exactly one variable, so each step is a single flip.
+    pub fn gray_rows(mut self) -> impl Iterator<Item = (u64, bool)> {
+        let row_count: u64 = 1 << self.variables.len();
+        let n = self.variables.len();
+
+        (0..row_count).map(move |step| {
+            if step > 0 {
+                // The single bit that differs from the previous Gray code
+                let changed = gray_code(step as u32) ^ gray_code(step as u32 - 1);
+                self.flip(n - 1 - changed.trailing_zeros() as usize);
+            }
+            (self.row, self.value())
+        })
+    }
+
+    fn flatten(&mut self, expr: &Expression) -> usize {
+        let node = match expr {
+            Expression::Val(value) => Node::Const(*value),
+            Expression::Var(v) => {
+                // Safe because variables holds every variable of the expression
+                let index = self.variables.iter().position(|known| known == v).unwrap();
+                Node::Var(index)
+            },
+            Expression::Neg(child) => Node::Neg(self.flatten(child)),
+            Expression::And(left, right) => Node::And(self.flatten(left), self.flatten(right)),
+            Expression::Or(left, right) => Node::Or(self.flatten(left), self.flatten(right)),
+            Expression::Xor(left, right) => Node::Xor(self.flatten(left), self.flatten(right)),
+            Expression::Implication(left, right) => Node::Implication(self.flatten(left), self.flatten(right)),
+            Expression::Equivalence(left, right) => Node::Equivalence(self.flatten(left), self.flatten(right)),
+        };
+
+        let index = self.nodes.len();
+        match node {
+            Node::Const(_) => {},
+            Node::Var(variable) => self.leaves[variable].push(index),
+            Node::Neg(child) => self.parents[child] = Some(index),
+            Node::And(left, right) |
+            Node::Or(left, right) |
+            Node::Xor(left, right) |
+            Node::Implication(left, right) |
+            Node::Equivalence(left, right) => {
+                self.parents[left] = Some(index);
+                self.parents[right] = Some(index);
+            },
+        }
+        self.nodes.push(node);
+        self.parents.push(None);
+        self.values.push(false);
+        index
+    }
+
+    // Value of a node from the current values of its children
+    fn compute(&self, index: usize) -> bool {
+        let values = &self.values;
+        match self.nodes[index] {
+            Node::Const(value) => value,
+            Node::Var(variable) => (self.row >> (self.variables.len() - 1 - variable)) & 1 == 1,
+            Node::Neg(child) => !values[child],
+            Node::And(left, right) => values[left] && values[right],
+            Node::Or(left, right) => values[left] || values[right],
+            Node::Xor(left, right) => values[left] != values[right],
+            Node::Implication(left, right) => !values[left] || values[right],
+            Node::Equivalence(left, right) => values[left] == values[right],
+        }
+    }
+}
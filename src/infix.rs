@@ -0,0 +1,252 @@
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use crate::variable::{is_identifier_start, read_identifier};
+use crate::{Expression, LogicError};
+
+// Binding power of each binary operator, from loosest to tightest:
+//   =  ⇔   equivalence        left associative
+//   >  ⇒   material condition right associative
+//   |  ∨   disjunction        left associative
+//   ^  ⊕   exclusive or       left associative
+//   &  ∧   conjunction        left associative
+//   !  ¬   negation           prefix, binds tighter than any binary operator
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinaryOp {
+    Equivalence,
+    Implication,
+    Or,
+    Xor,
+    And,
+}
+
+impl BinaryOp {
+    fn from_symbol(symbol: char) -> Option<Self> {
+        match symbol {
+            '=' | '⇔' => Some(BinaryOp::Equivalence),
+            '>' | '⇒' => Some(BinaryOp::Implication),
+            '|' | '∨' => Some(BinaryOp::Or),
+            '^' | '⊕' => Some(BinaryOp::Xor),
+            '&' | '∧' => Some(BinaryOp::And),
+            _ => None,
+        }
+    }
+
+    fn precedence(self) -> u8 {
+        match self {
+            BinaryOp::Equivalence => 1,
+            BinaryOp::Implication => 2,
+            BinaryOp::Or => 3,
+            BinaryOp::Xor => 4,
+            BinaryOp::And => 5,
+        }
+    }
+
+    fn is_right_associative(self) -> bool {
+        self == BinaryOp::Implication
+    }
+
+    fn build(self, left: Expression, right: Expression) -> Expression {
+        match self {
+            BinaryOp::Equivalence => Expression::equiv(left, right),
+            BinaryOp::Implication => Expression::implies(left, right),
+            BinaryOp::Or => left | right,
+            BinaryOp::Xor => Expression::xor(left, right),
+            BinaryOp::And => left & right,
+        }
+    }
+}
+
+const NEGATION_PRECEDENCE: u8 = 6;
+
+// Precedence climbing parser over the characters of the formula.
+// m = formula length
+// Space complexity: O(m) - expression tree + recursion depth bounded by nesting
+// Time complexity: O(m) - every character is consumed exactly once
+struct Parser<'a> {
+    formula: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+// An operator still waiting for its right-most operand: (position, symbol, arity)
+type Pending = Option<(usize, char, usize)>;
+
+impl<'a> Parser<'a> {
+    fn new(formula: &'a str) -> Self {
+        Parser { formula, chars: formula.char_indices().peekable() }
+    }
+
+    fn peek(&mut self) -> Option<(usize, char)> {
+        while let Some(&(position, c)) = self.chars.peek() {
+            if !c.is_whitespace() {
+                return Some((position, c));
+            }
+            self.chars.next();
+        }
+        None
+    }
+
+    fn parse_expression(&mut self, min_precedence: u8, pending: Pending) -> Result<Expression, LogicError> {
+        let mut left = self.parse_operand(pending)?;
+
+        while let Some((position, symbol)) = self.peek() {
+            let Some(op) = BinaryOp::from_symbol(symbol) else {
+                break;
+            };
+            if op.precedence() < min_precedence {
+                break;
+            }
+            self.chars.next();
+
+            let next_precedence = if op.is_right_associative() {
+                op.precedence()
+            } else {
+                op.precedence() + 1
+            };
+            let right = self.parse_expression(next_precedence, Some((position, symbol, 2)))?;
+            left = op.build(left, right);
+        }
+
+        Ok(left)
+    }
+
+    fn parse_operand(&mut self, pending: Pending) -> Result<Expression, LogicError> {
+        let missing_operand = |position: usize| match pending {
+            Some((position, symbol, arity)) => {
+                LogicError::MissingArgument { position, symbol, expected: arity, found: arity - 1 }
+            },
+            None => LogicError::IncompleteFormula { position, depth: 0 },
+        };
+
+        let Some((position, character)) = self.peek() else {
+            return Err(missing_operand(self.formula.len()));
+        };
+
+        match character {
+            // Constants
+            '1' | '⊤' => {
+                self.chars.next();
+                Ok(Expression::val(true))
+            },
+            '0' | '⊥' => {
+                self.chars.next();
+                Ok(Expression::val(false))
+            },
+
+            // Variables
+            'A'..='Z' => {
+                self.chars.next();
+                Ok(Expression::var(character))
+            },
+            c if is_identifier_start(c) => {
+                let (variable, end) = read_identifier(self.formula, position);
+                while self.chars.next_if(|&(offset, _)| offset < end).is_some() {}
+                Ok(Expression::Var(variable))
+            },
+
+            // Negation
+            '!' | '¬' => {
+                self.chars.next();
+                let operand = self.parse_expression(NEGATION_PRECEDENCE, Some((position, character, 1)))?;
+                Ok(!operand)
+            },
+
+            // Parenthesized sub-formula
+            '(' => {
+                self.chars.next();
+                let inner = self.parse_expression(0, None)?;
+                match self.peek() {
+                    Some((_, ')')) => {
+                        self.chars.next();
+                        Ok(inner)
+                    },
+                    Some((other, symbol)) => Err(unexpected_symbol(other, symbol)),
+                    None => Err(LogicError::UnbalancedParenthesis { position }),
+                }
+            },
+
+            // A binary operator or a closing parenthesis where an operand was expected
+            ')' if pending.is_none() => Err(LogicError::UnbalancedParenthesis { position }),
+            ')' => Err(missing_operand(position)),
+            _ if BinaryOp::from_symbol(character).is_some() => match pending {
+                Some(_) => Err(missing_operand(position)),
+                None => Err(LogicError::MissingArgument { position, symbol: character, expected: 2, found: 0 }),
+            },
+
+            // Unrecognized character
+            _ => Err(LogicError::UnrecognizedSymbol { position, symbol: character }),
+        }
+    }
+}
+
+// Error for a symbol left over once a complete operand has been parsed,
+// where only a binary operator or a closing parenthesis could follow
+fn unexpected_symbol(position: usize, symbol: char) -> LogicError {
+    match symbol {
+        ')' => LogicError::UnbalancedParenthesis { position },
+        // Two operands with no operator to join them
+        'A'..='Z' | '0' | '1' | '⊤' | '⊥' | '!' | '¬' | '(' => LogicError::IncompleteFormula { position, depth: 2 },
+        c if is_identifier_start(c) => LogicError::IncompleteFormula { position, depth: 2 },
+        _ => LogicError::UnrecognizedSymbol { position, symbol },
+    }
+}
+
+impl Expression {
+    pub fn from_infix(formula: &str) -> Result<Self, LogicError> {
+        let mut parser = Parser::new(formula);
+        let expression = parser.parse_expression(0, None)?;
+
+        // Anything left over could not be attached to the tree
+        match parser.peek() {
+            None => Ok(expression),
+            Some((position, symbol)) => Err(unexpected_symbol(position, symbol)),
+        }
+    }
+
+    // Renders the tree with the fewest parentheses that still parse back
+    // to the same tree with `from_infix`.
+    pub fn to_infix(&self) -> String {
+        match self {
+            Expression::Val(_) | Expression::Var(_) => self.to_unicode(),
+            Expression::Neg(child) => {
+                format!("{}{}", self.to_unicode(), child.infix_operand(NEGATION_PRECEDENCE))
+            },
+            Expression::And(left, right) |
+            Expression::Or(left, right) |
+            Expression::Xor(left, right) |
+            Expression::Implication(left, right) |
+            Expression::Equivalence(left, right) => {
+                let op = self.binary_op().unwrap(); // Safe because self is a binary node
+                let (left_min, right_min) = if op.is_right_associative() {
+                    (op.precedence() + 1, op.precedence())
+                } else {
+                    (op.precedence(), op.precedence() + 1)
+                };
+                format!(
+                    "{} {} {}",
+                    left.infix_operand(left_min),
+                    self.to_unicode(),
+                    right.infix_operand(right_min)
+                )
+            },
+        }
+    }
+
+    fn infix_operand(&self, min_precedence: u8) -> String {
+        match self.binary_op() {
+            Some(op) if op.precedence() < min_precedence => format!("({})", self.to_infix()),
+            _ => self.to_infix(),
+        }
+    }
+
+    fn binary_op(&self) -> Option<BinaryOp> {
+        match self {
+            Expression::And(_, _) => Some(BinaryOp::And),
+            Expression::Or(_, _) => Some(BinaryOp::Or),
+            Expression::Xor(_, _) => Some(BinaryOp::Xor),
+            Expression::Implication(_, _) => Some(BinaryOp::Implication),
+            Expression::Equivalence(_, _) => Some(BinaryOp::Equivalence),
+            _ => None,
+        }
+    }
+}
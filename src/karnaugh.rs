@@ -0,0 +1,260 @@
+use crate::cube::Cube;
+use crate::minimize::minimum_cover;
+use crate::truth_table::{escape_html, TruthTable};
+use crate::{gray_code, Expression, LogicError, Variable};
+
+// Largest map drawn: 8 x 8 cells
+pub const MAX_KARNAUGH_VARIABLES: usize = 6;
+
+// Cell size of the SVG rendering, in pixels
+const CELL: usize = 40;
+
+// Group outlines of the SVG rendering, reused in order
+const COLORS: [&str; 8] = ["#e6194b", "#3cb44b", "#4363d8", "#f58231", "#911eb4", "#42d4f4", "#f032e6", "#9a6324"];
+
+// The truth table of a function laid out as a grid whose rows and columns
+// follow Gray code order, so cells next to each other differ by one variable.
+// The first n / 2 variables select the row and the others the column:
+// with (A, B, C, D) rows are AB = 00, 01, 11, 10 and columns CD likewise.
+// Groups are cubes outlined on the map, typically a minimal cover.
+// Don't care cells show '-'.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KarnaughMap {
+    table: TruthTable,
+    groups: Vec<Cube>,
+}
+
+impl KarnaughMap {
+    pub fn from_expression(expr: &Expression) -> Result<Self, LogicError> {
+        KarnaughMap::from_table(&TruthTable::from_expression(expr)?)
+    }
+
+    pub fn from_table(table: &TruthTable) -> Result<Self, LogicError> {
+        let count = table.variables().len();
+        if count > MAX_KARNAUGH_VARIABLES {
+            return Err(LogicError::TooManyVariables { count, max: MAX_KARNAUGH_VARIABLES });
+        }
+        // Intermediate columns are not drawn
+        let mut inputs = TruthTable::from_column(table.variables().to_vec(), table.output().clone());
+        if let Some(dont_care) = table.dont_care() {
+            inputs = inputs.with_dont_care(dont_care.clone());
+        }
+        Ok(KarnaughMap { table: inputs, groups: Vec::new() })
+    }
+
+    pub fn with_groups(mut self, groups: Vec<Cube>) -> Self {
+        self.groups = groups;
+        self
+    }
+
+    // Outlines the minimal cover found by Quine-McCluskey
+    pub fn with_minimal_cover(self) -> Self {
+        let cover = minimum_cover(&self.table.prime_implicants(), &self.table.minterms());
+        self.with_groups(cover)
+    }
+
+    pub fn variables(&self) -> &[Variable] {
+        self.table.variables()
+    }
+
+    pub fn row_variables(&self) -> &[Variable] {
+        &self.variables()[..self.row_bits()]
+    }
+
+    pub fn column_variables(&self) -> &[Variable] {
+        &self.variables()[self.row_bits()..]
+    }
+
+    pub fn groups(&self) -> &[Cube] {
+        &self.groups
+    }
+
+    pub fn row_count(&self) -> usize {
+        1 << self.row_bits()
+    }
+
+    pub fn column_count(&self) -> usize {
+        1 << self.column_bits()
+    }
+
+    // Truth table row shown in a cell
+    pub fn table_row(&self, row: usize, column: usize) -> u64 {
+        ((gray_code(row as u32) as u64) << self.column_bits()) | gray_code(column as u32) as u64
+    }
+
+    pub fn value(&self, row: usize, column: usize) -> bool {
+        self.table.output().get(self.table_row(row, column))
+    }
+
+    pub fn is_dont_care(&self, row: usize, column: usize) -> bool {
+        self.table.is_dont_care(self.table_row(row, column))
+    }
+
+    // "0", "1" or "-"
+    fn cell(&self, row: usize, column: usize) -> &'static str {
+        match (self.is_dont_care(row, column), self.value(row, column)) {
+            (true, _) => "-",
+            (false, true) => "1",
+            (false, false) => "0",
+        }
+    }
+
+    // Value of each cell followed by the letters of the groups covering it,
+    // the groups listed below the map
+    //
+    // AB\CD | 00 | 01 | 11 | 10
+    //    00 | 0  | 1a | 1a | 0
+    pub fn to_text(&self) -> String {
+        let cells: Vec<Vec<String>> = (0..self.row_count())
+            .map(|row| {
+                (0..self.column_count())
+                    .map(|column| {
+                        let table_row = self.table_row(row, column);
+                        let mut cell = self.cell(row, column).to_string();
+                        for (index, group) in self.groups.iter().enumerate() {
+                            if group.covers(table_row) {
+                                cell.push(group_label(index));
+                            }
+                        }
+                        cell
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let corner = format!("{}\\{}", names(self.row_variables()), names(self.column_variables()));
+        let row_width = corner.chars().count().max(self.row_bits());
+        let width = cells.iter().flatten().map(String::len).max().unwrap_or(1).max(self.column_bits());
+
+        let mut lines = Vec::new();
+        let mut header = format!("{:>row_width$}", corner);
+        for column in 0..self.column_count() {
+            header.push_str(&format!(" | {:<width$}", label(column, self.column_bits())));
+        }
+        lines.push(header);
+        for (row, cells) in cells.iter().enumerate() {
+            let mut line = format!("{:>row_width$}", label(row, self.row_bits()));
+            for cell in cells {
+                line.push_str(&format!(" | {:<width$}", cell));
+            }
+            lines.push(line);
+        }
+
+        let mut result = String::new();
+        for line in lines {
+            result.push_str(line.trim_end());
+            result.push('\n');
+        }
+        for (index, group) in self.groups.iter().enumerate() {
+            result.push_str(&format!("{}: {}\n", group_label(index), group.to_product(self.variables()).to_infix()));
+        }
+        result
+    }
+
+    // The grid with its headers, each group drawn as rounded rectangles over
+    // its cells. A group wrapping around an edge of the map is split into one
+    // rectangle per side.
+    pub fn to_svg(&self) -> String {
+        let left = CELL * 2;
+        let top = CELL;
+        let width = left + self.column_count() * CELL;
+        let height = top + self.row_count() * CELL;
+
+        let mut result = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" font-family=\"monospace\" font-size=\"14\" text-anchor=\"middle\" dominant-baseline=\"middle\">\n"
+        );
+        let corner = format!("{}\\{}", names(self.row_variables()), names(self.column_variables()));
+        result.push_str(&format!("  <text x=\"{}\" y=\"{}\">{}</text>\n", left / 2, top / 2, escape_html(&corner)));
+
+        for column in 0..self.column_count() {
+            let x = left + column * CELL + CELL / 2;
+            result.push_str(&format!("  <text x=\"{}\" y=\"{}\">{}</text>\n", x, top / 2, label(column, self.column_bits())));
+        }
+        for row in 0..self.row_count() {
+            let y = top + row * CELL + CELL / 2;
+            result.push_str(&format!("  <text x=\"{}\" y=\"{}\">{}</text>\n", left / 2, y, label(row, self.row_bits())));
+            for column in 0..self.column_count() {
+                let x = left + column * CELL;
+                result.push_str(&format!(
+                    "  <rect x=\"{}\" y=\"{}\" width=\"{CELL}\" height=\"{CELL}\" fill=\"none\" stroke=\"black\"/>\n",
+                    x,
+                    top + row * CELL
+                ));
+                result.push_str(&format!("  <text x=\"{}\" y=\"{}\">{}</text>\n", x + CELL / 2, y, self.cell(row, column)));
+            }
+        }
+
+        for (index, group) in self.groups.iter().enumerate() {
+            let color = COLORS[index % COLORS.len()];
+            let rows: Vec<usize> = (0..self.row_count()).filter(|&row| self.covers_row(group, row)).collect();
+            let columns: Vec<usize> = (0..self.column_count()).filter(|&column| self.covers_column(group, column)).collect();
+            let inset = 3 + 3 * (index % 4);
+
+            for (first_row, last_row) in runs(&rows) {
+                for (first_column, last_column) in runs(&columns) {
+                    result.push_str(&format!(
+                        "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" fill=\"{color}\" fill-opacity=\"0.15\" stroke=\"{color}\" stroke-width=\"2\"/>\n",
+                        left + first_column * CELL + inset,
+                        top + first_row * CELL + inset,
+                        (last_column - first_column + 1) * CELL - 2 * inset,
+                        (last_row - first_row + 1) * CELL - 2 * inset,
+                        CELL / 4,
+                    ));
+                }
+            }
+        }
+
+        result.push_str("</svg>\n");
+        result
+    }
+
+    fn row_bits(&self) -> usize {
+        self.variables().len() / 2
+    }
+
+    fn column_bits(&self) -> usize {
+        self.variables().len() - self.row_bits()
+    }
+
+    // The row variables of the group match this map row
+    fn covers_row(&self, group: &Cube, row: usize) -> bool {
+        let high = !((1 << self.column_bits()) - 1);
+        ((gray_code(row as u32) as u64) << self.column_bits()) & group.mask & high == group.value & high
+    }
+
+    fn covers_column(&self, group: &Cube, column: usize) -> bool {
+        let low = (1 << self.column_bits()) - 1;
+        gray_code(column as u32) as u64 & group.mask & low == group.value & low
+    }
+}
+
+// Gray code of `index` on `bits` digits, empty for a single row or column
+fn label(index: usize, bits: usize) -> String {
+    if bits == 0 {
+        return String::new();
+    }
+    format!("{:0bits$b}", gray_code(index as u32))
+}
+
+fn group_label(index: usize) -> char {
+    let letters: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    letters[index % letters.len()]
+}
+
+// Letters side by side, names separated by commas
+fn names(variables: &[Variable]) -> String {
+    let names: Vec<String> = variables.iter().map(Variable::to_string).collect();
+    if variables.iter().all(Variable::is_letter) { names.concat() } else { names.join(",") }
+}
+
+// Maximal runs of consecutive values in a sorted list, as (first, last)
+fn runs(values: &[usize]) -> Vec<(usize, usize)> {
+    let mut result: Vec<(usize, usize)> = Vec::new();
+    for &value in values {
+        match result.last_mut() {
+            Some((_, last)) if *last + 1 == value => *last = value,
+            _ => result.push((value, value)),
+        }
+    }
+    result
+}
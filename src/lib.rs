@@ -6,18 +6,84 @@ pub mod error;
 pub mod truth_table;
 pub mod negation_normal_form;
 pub mod conjunctive_normal_form;
+pub mod disjunctive_normal_form;
 pub mod sat;
 pub mod expression;
+pub mod infix;
 pub mod set;
 pub mod curve;
+pub mod variable;
+pub mod assignment;
+pub mod program;
+pub mod bitslice;
+pub mod incremental;
+pub mod synthesis;
+pub mod cube;
+pub mod minimize;
+pub mod espresso;
+pub mod pla;
+pub mod karnaugh;
+pub mod dont_care;
+pub mod cnf;
+pub mod tseitin;
+pub mod dimacs;
+pub mod dpll;
+pub mod cdcl;
+pub mod biguint;
+pub mod model_count;
 
 pub use arithmetic::*;
-pub use evaluate::eval_formula;
+pub use evaluate::{eval_formula, eval_formula_with, try_eval_formula, try_eval_formula_with};
 pub use error::LogicError;
 pub use negation_normal_form::*;
 pub use conjunctive_normal_form::*;
+pub use disjunctive_normal_form::*;
 pub use expression::*;
+pub use variable::Variable;
+pub use assignment::Assignment;
+pub use program::Program;
+pub use bitslice::TruthColumn;
+pub use synthesis::{product_of_sums, sum_of_products};
+pub use cube::Cube;
+pub use minimize::{minimal_sum_of_products, try_minimal_sum_of_products};
+pub use espresso::{heuristic_sum_of_products, try_heuristic_sum_of_products};
+pub use pla::Pla;
+pub use karnaugh::KarnaughMap;
+pub use dont_care::IncompleteFunction;
+pub use cnf::{Clause, Cnf, Literal};
+pub use tseitin::Tseitin;
+pub use biguint::BigUint;
+pub use model_count::{model_count, try_model_count};
 
+// Former helper without the operator, reported at position 0 as '?'
+#[deprecated(note = "use pop_operand, pop_operands or pop_result, which report where the operand is missing")]
 pub fn pop_from_stack<T>(stack: &mut Vec<T>) -> Result<T, LogicError> {
-    stack.pop().ok_or(LogicError::MissingArgument)
+    pop_operand(stack, 0, '?')
+}
+
+// Pops the operand of the unary operator `symbol` found at `position`
+pub fn pop_operand<T>(stack: &mut Vec<T>, position: usize, symbol: char) -> Result<T, LogicError> {
+    stack.pop().ok_or(LogicError::MissingArgument { position, symbol, expected: 1, found: 0 })
+}
+
+// Pops the (left, right) operands of the binary operator `symbol` found at `position`
+pub fn pop_operands<T>(stack: &mut Vec<T>, position: usize, symbol: char) -> Result<(T, T), LogicError> {
+    if stack.len() < 2 {
+        return Err(LogicError::MissingArgument { position, symbol, expected: 2, found: stack.len() });
+    }
+
+    // Safe because we just checked stack.len() >= 2
+    let right = stack.pop().unwrap();
+    let left = stack.pop().unwrap();
+    Ok((left, right))
+}
+
+// Makes sure the whole formula reduced to exactly one value and returns it
+pub fn pop_result<T>(stack: &mut Vec<T>, formula: &str) -> Result<T, LogicError> {
+    if stack.len() != 1 {
+        return Err(LogicError::IncompleteFormula { position: formula.len(), depth: stack.len() });
+    }
+
+    // Safe because we just checked stack.len() == 1
+    Ok(stack.pop().unwrap())
 }
@@ -0,0 +1,163 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::cube::{cover_to_expression, Cube};
+use crate::truth_table::TruthTable;
+use crate::{Expression, LogicError};
+
+// Two-level minimization with Quine-McCluskey and Petrick's method.
+// The result is a sum of products with as few products as possible, then as
+// few literals as possible among those.
+// v = variable count
+// Space complexity: O(3^v) - every cube over v variables may be a prime implicant
+// Time complexity: exponential in v, and Petrick's method in the number of primes
+pub fn minimal_sum_of_products(formula: &str) -> String {
+    match try_minimal_sum_of_products(formula) {
+        Ok(value) => value,
+        Err(err) => {
+            eprintln!("{}", err.render(formula));
+            String::new()
+        }
+    }
+}
+
+pub fn try_minimal_sum_of_products(formula: &str) -> Result<String, LogicError> {
+    let expression = Expression::from_formula(formula)?;
+    Ok(expression.to_minimal_dnf()?.to_rpn())
+}
+
+impl Expression {
+    // Equivalent minimal sum of products, over the variables of the expression
+    pub fn to_minimal_dnf(&self) -> Result<Expression, LogicError> {
+        Ok(TruthTable::from_expression(self)?.to_minimal_dnf())
+    }
+}
+
+impl TruthTable {
+    // Don't care rows can be part of any implicant, so they are merged with
+    // the minterms. Primes covering only don't cares are of no use and dropped.
+    pub fn prime_implicants(&self) -> Vec<Cube> {
+        let minterms = self.minterms();
+        let mut primes = prime_implicants(self.variables().len(), &[minterms.clone(), self.dont_cares()].concat());
+        primes.retain(|prime| minterms.iter().any(|&row| prime.covers(row)));
+        primes
+    }
+
+    // Don't cares take whichever value gives the smaller formula
+    pub fn to_minimal_dnf(&self) -> Expression {
+        cover_to_expression(&minimum_cover(&self.prime_implicants(), &self.minterms()), self.variables())
+    }
+}
+
+// Every cube covering only minterms that is not contained in a larger such cube.
+// Cubes are merged in rounds: two cubes with the same variables whose values
+// differ in one bit combine into a cube without that variable. Only cubes
+// whose count of ones differs by one can combine, so each round pairs up
+// neighbouring groups. Cubes left uncombined at the end of a round are prime.
+// Sorted, so results are deterministic.
+pub fn prime_implicants(variable_count: usize, minterms: &[u64]) -> Vec<Cube> {
+    let mut current: HashSet<Cube> = minterms.iter().map(|&row| Cube::minterm(row, variable_count)).collect();
+    let mut primes = Vec::new();
+
+    while !current.is_empty() {
+        let mut groups: HashMap<(u64, u32), Vec<Cube>> = HashMap::new();
+        for cube in &current {
+            groups.entry((cube.mask, cube.value.count_ones())).or_default().push(*cube);
+        }
+
+        let mut next = HashSet::new();
+        let mut combined = HashSet::new();
+        for (&(mask, ones), cubes) in &groups {
+            let Some(neighbours) = groups.get(&(mask, ones + 1)) else {
+                continue;
+            };
+            for cube in cubes {
+                for neighbour in neighbours {
+                    if let Some(merged) = cube.combine(neighbour) {
+                        next.insert(merged);
+                        combined.insert(*cube);
+                        combined.insert(*neighbour);
+                    }
+                }
+            }
+        }
+
+        primes.extend(current.difference(&combined));
+        current = next;
+    }
+
+    sort_cover(&mut primes);
+    primes
+}
+
+// Smallest set of `primes` covering every minterm.
+// Essential primes, the only ones covering some minterm, are always taken.
+// The remaining minterms go through Petrick's method: the condition
+// "minterm m is covered" is a sum over the primes covering m, their product is
+// expanded into a sum of products with absorption (X + XY = X), and the
+// cheapest product is the cover.
+pub fn minimum_cover(primes: &[Cube], minterms: &[u64]) -> Vec<Cube> {
+    let covering = |row: u64| -> Vec<usize> { (0..primes.len()).filter(|&i| primes[i].covers(row)).collect() };
+
+    let mut chosen: BTreeSet<usize> = BTreeSet::new();
+    for &row in minterms {
+        if let [only] = covering(row)[..] {
+            chosen.insert(only);
+        }
+    }
+
+    let remaining: Vec<u64> = minterms
+        .iter()
+        .copied()
+        .filter(|&row| !chosen.iter().any(|&i| primes[i].covers(row)))
+        .collect();
+
+    let mut products: Vec<BTreeSet<usize>> = vec![BTreeSet::new()];
+    for row in remaining {
+        let sum = covering(row);
+        let mut expanded: Vec<BTreeSet<usize>> = Vec::new();
+        for product in &products {
+            // Already covered by a prime of this product: X (X + Y) = X
+            if sum.iter().any(|i| product.contains(i)) {
+                expanded.push(product.clone());
+                continue;
+            }
+            for &i in &sum {
+                let mut extended = product.clone();
+                extended.insert(i);
+                expanded.push(extended);
+            }
+        }
+        products = absorb(expanded);
+    }
+
+    let cost = |product: &BTreeSet<usize>| {
+        let literals: u32 = product.iter().map(|&i| primes[i].literal_count()).sum();
+        (product.len(), literals)
+    };
+    // products is never empty, it starts with the empty product
+    let best = products.into_iter().min_by_key(cost).unwrap();
+
+    let mut cover: Vec<Cube> = chosen.union(&best).map(|&i| primes[i]).collect();
+    sort_cover(&mut cover);
+    cover
+}
+
+// Drops duplicates and every product containing another one
+fn absorb(mut products: Vec<BTreeSet<usize>>) -> Vec<BTreeSet<usize>> {
+    products.sort();
+    products.dedup();
+    products.sort_by_key(BTreeSet::len);
+
+    let mut kept: Vec<BTreeSet<usize>> = Vec::new();
+    for product in products {
+        if !kept.iter().any(|smaller| smaller.is_subset(&product)) {
+            kept.push(product);
+        }
+    }
+    kept
+}
+
+// Larger cubes first, then by value: A | B rather than B | A
+fn sort_cover(cover: &mut [Cube]) {
+    cover.sort_by_key(|cube| (cube.literal_count(), std::cmp::Reverse(cube.mask), cube.value));
+}
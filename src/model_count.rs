@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+
+use crate::biguint::BigUint;
+use crate::bitslice::{block_count, MAX_BITSLICED_VARIABLES};
+use crate::cnf::{Clause, Cnf, Literal};
+use crate::dpll::Solver;
+use crate::{Expression, LogicError, Program};
+
+// Expressions with at most this many variables are counted on their truth table
+pub const BITSLICED_COUNT_VARIABLES: usize = 20;
+
+// Number of assignments of the variables of the formula making it true
+pub fn model_count(formula: &str) -> BigUint {
+    match try_model_count(formula) {
+        Ok(value) => value,
+        Err(err) => {
+            eprintln!("{}", err.render(formula));
+            BigUint::zero()
+        }
+    }
+}
+
+pub fn try_model_count(formula: &str) -> Result<BigUint, LogicError> {
+    Ok(count_models(&Expression::from_formula(formula)?))
+}
+
+// Small expressions go through their bit-sliced truth table, the others
+// through count_cnf on their Tseitin encoding, which has exactly one model
+// per model of the expression as every auxiliary variable is equivalent to
+// its subexpression.
+pub fn count_models(expression: &Expression) -> BigUint {
+    if expression.variables().len() <= BITSLICED_COUNT_VARIABLES {
+        // Within MAX_BITSLICED_VARIABLES
+        return count_models_bitsliced(expression).unwrap();
+    }
+    count_cnf(expression.to_tseitin().cnf())
+}
+
+// Counts the true rows 64 at a time without storing the table
+// v = variable count, m = size of the expression
+// Space complexity: O(m + v)
+// Time complexity: O(2^v / 64 . m)
+pub fn count_models_bitsliced(expression: &Expression) -> Result<BigUint, LogicError> {
+    let program = Program::compile(expression)?;
+    let n = program.variables().len();
+    if n > MAX_BITSLICED_VARIABLES {
+        return Err(LogicError::TooManyVariables { count: n, max: MAX_BITSLICED_VARIABLES });
+    }
+    let count: u64 = (0..block_count(n)).map(|block| program.evaluate_block(block).count_ones() as u64).sum();
+    Ok(BigUint::from(count))
+}
+
+// Evaluates every row one at a time, the reference the counters are checked against
+// Time complexity: O(2^v . m)
+pub fn count_models_enumeration(expression: &Expression) -> Result<BigUint, LogicError> {
+    let program = Program::compile(expression)?;
+    let count = (0..program.row_count()).filter(|&row| program.evaluate(row as u64)).count();
+    Ok(BigUint::from(count as u64))
+}
+
+// Number of assignments of all the variables of the CNF satisfying it.
+// DPLL-style search that sums the counts of both values of a variable, with:
+// - unit propagation on the trail of the DPLL solver, the opposite value of
+//   a forced literal having no model
+// - component decomposition: open clauses sharing no unassigned variable are
+//   counted separately and their counts multiplied
+// - a cache of the count of every component already met, as the same
+//   residual formula shows up under many partial assignments
+// Variables appearing in no open clause double the count.
+// Only decisions recurse, forced literals are undone with the trail.
+// Time complexity: exponential in the worst case, as #SAT is #P-complete
+pub fn count_cnf(cnf: &Cnf) -> BigUint {
+    let mut normalized = Cnf::new(cnf.variables().to_vec());
+    for clause in cnf.clauses() {
+        let mut clause = clause.clone();
+        clause.sort();
+        clause.dedup();
+        if clause.windows(2).any(|pair| pair[0] == !pair[1]) {
+            continue;
+        }
+        normalized.add_clause(clause);
+    }
+
+    let mut counter = Counter {
+        solver: Solver::new(&normalized),
+        clauses: normalized.clauses(),
+        parents: (0..normalized.variables().len()).collect(),
+        cache: HashMap::new(),
+    };
+    if !counter.solver.assign_units() || !counter.solver.propagate() {
+        return BigUint::zero();
+    }
+    let unassigned = normalized.variables().len() - counter.solver.trail_length();
+    let all: Vec<usize> = (0..normalized.clauses().len()).collect();
+    counter.count_all(&all, unassigned)
+}
+
+struct Counter<'a> {
+    solver: Solver<'a>,
+    // Sorted, without duplicate literals nor tautologies
+    clauses: &'a [Clause],
+    // Union-find over the variables, only meaningful for those of the clauses being split
+    parents: Vec<usize>,
+    // Model count of each component, keyed by its sorted residual clauses
+    cache: HashMap<Vec<Clause>, BigUint>,
+}
+
+// Open clauses connected through their unassigned variables
+struct Component {
+    clauses: Vec<usize>,
+    variable_count: usize,
+}
+
+impl Counter<'_> {
+    // Models over `variable_count` unassigned variables, holding those of the
+    // open clauses among `clauses`, after propagation
+    fn count_all(&mut self, clauses: &[usize], variable_count: usize) -> BigUint {
+        let components = self.components(clauses);
+        let free = variable_count - components.iter().map(|component| component.variable_count).sum::<usize>();
+
+        let mut product = BigUint::one();
+        for component in components {
+            product = &product * &self.count(&component);
+            if product.is_zero() {
+                break;
+            }
+        }
+        product << free
+    }
+
+    fn count(&mut self, component: &Component) -> BigUint {
+        let mut key: Vec<Clause> = component.clauses.iter().map(|&index| self.residual(index)).collect();
+        key.sort();
+        if let Some(count) = self.cache.get(&key) {
+            return count.clone();
+        }
+
+        let mut occurrences: HashMap<usize, usize> = HashMap::new();
+        for literal in key.iter().flatten() {
+            *occurrences.entry(literal.variable()).or_default() += 1;
+        }
+        let (&variable, _) = occurrences.iter().max_by_key(|&(&variable, &count)| (count, std::cmp::Reverse(variable))).unwrap(); // Open clauses have unassigned literals
+
+        let mut count = BigUint::zero();
+        for literal in [Literal::positive(variable), Literal::negative(variable)] {
+            let length = self.solver.trail_length();
+            self.solver.assign(literal);
+            if self.solver.propagate() {
+                let assigned = self.solver.trail_length() - length;
+                count = count + self.count_all(&component.clauses, component.variable_count - assigned);
+            }
+            self.solver.undo(length);
+        }
+        self.cache.insert(key, count.clone());
+        count
+    }
+
+    // Unassigned literals of a clause
+    fn residual(&self, index: usize) -> Clause {
+        self.clauses[index].iter().copied().filter(|&literal| self.solver.value(literal).is_none()).collect()
+    }
+
+    fn is_open(&self, index: usize) -> bool {
+        self.clauses[index].iter().all(|&literal| self.solver.value(literal) != Some(true))
+    }
+
+    // Groups of open clauses connected through shared unassigned variables.
+    // After propagation an open clause has at least two unassigned literals.
+    // c = literals of the clauses
+    // Time complexity: O(c log c)
+    fn components(&mut self, clauses: &[usize]) -> Vec<Component> {
+        let open: Vec<usize> = clauses.iter().copied().filter(|&index| self.is_open(index)).collect();
+        let mut variables = Vec::new();
+        for &index in &open {
+            for &literal in &self.clauses[index] {
+                if self.solver.value(literal).is_none() {
+                    self.parents[literal.variable()] = literal.variable();
+                    variables.push(literal.variable());
+                }
+            }
+        }
+        for &index in &open {
+            let mut unassigned = self.clauses[index].iter().filter(|&&literal| self.solver.value(literal).is_none());
+            let first = find(&mut self.parents, unassigned.next().unwrap().variable());
+            for literal in unassigned {
+                let root = find(&mut self.parents, literal.variable());
+                self.parents[root] = first;
+            }
+        }
+
+        let mut groups: HashMap<usize, Component> = HashMap::new();
+        for &index in &open {
+            let variable = self.clauses[index].iter().find(|&&literal| self.solver.value(literal).is_none()).unwrap().variable();
+            let root = find(&mut self.parents, variable);
+            groups.entry(root).or_insert_with(|| Component { clauses: Vec::new(), variable_count: 0 }).clauses.push(index);
+        }
+        variables.sort_unstable();
+        variables.dedup();
+        for variable in variables {
+            let root = find(&mut self.parents, variable);
+            groups.get_mut(&root).unwrap().variable_count += 1; // Every variable comes from a clause of its group
+        }
+        groups.into_values().collect()
+    }
+}
+
+// Root of the set of `variable`, halving the path on the way
+fn find(parents: &mut [usize], mut variable: usize) -> usize {
+    while parents[variable] != variable {
+        parents[variable] = parents[parents[variable]];
+        variable = parents[variable];
+    }
+    variable
+}
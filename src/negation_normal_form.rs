@@ -1,4 +1,4 @@
-use crate::Expression;
+use crate::{Expression, LogicError};
 
 // Space complexity: O(m): O(m) for expression tree + O(m) for call stack
 // Time complexity:  O(m)
@@ -7,15 +7,18 @@ use crate::Expression;
 //   Unary nodes - 1 recursive call
 //   Binary nodes - 2 recursive calls
 pub fn negation_normal_form(formula: &str) -> String {
-    let expression = match Expression::from_formula(formula) {
+    match try_negation_normal_form(formula) {
         Ok(value) => value,
         Err(err) => {
-            eprintln!("Error occurred while evaluating: {err:?}");
-            return String::new();
+            eprintln!("{}", err.render(formula));
+            String::new()
         }
-    };
+    }
+}
 
-    normalize(&expression).to_rpn()
+pub fn try_negation_normal_form(formula: &str) -> Result<String, LogicError> {
+    let expression = Expression::from_formula(formula)?;
+    Ok(normalize(&expression).to_rpn())
 }
 
 // Double negation: ¬¬A ⇔ A
@@ -44,7 +47,7 @@ pub fn normalize(expr: &Expression) -> Expression {
 fn negate(expr: &Expression) -> Expression {
     match expr {
         Expression::Val(x) => Expression::val(!x),
-        Expression::Var(x) => !Expression::var(*x),
+        Expression::Var(x) => !Expression::var(x.clone()),
         Expression::Neg(e) => normalize(e),
         Expression::And(a, b) => negate(a) | negate(b),
         Expression::Or(a, b) => negate(a) & negate(b),
@@ -54,3 +57,45 @@ fn negate(expr: &Expression) -> Expression {
     }
 }
 
+// Sign a subformula takes in the NNF of the whole formula: under an even
+// number of negations, an odd number, or both when normalize copies it once
+// of each sign, as it does for the operands of XOR and equivalence
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Polarity {
+    Positive,
+    Negative,
+    Both,
+}
+
+impl Polarity {
+    pub fn flip(self) -> Polarity {
+        match self {
+            Polarity::Positive => Polarity::Negative,
+            Polarity::Negative => Polarity::Positive,
+            Polarity::Both => Polarity::Both,
+        }
+    }
+
+    pub fn has_positive(self) -> bool {
+        self != Polarity::Negative
+    }
+
+    pub fn has_negative(self) -> bool {
+        self != Polarity::Positive
+    }
+}
+
+// Polarities of the operands of `expr` when it has `polarity`, following the
+// rewrites of normalize and negate: a negation flips its operand, AND and OR
+// keep theirs, an implication ¬A ∨ B flips its left side only, and XOR and
+// equivalence use both signs of each operand.
+// The operand of a negation is returned twice.
+pub fn operand_polarities(expr: &Expression, polarity: Polarity) -> (Polarity, Polarity) {
+    match expr {
+        Expression::Val(_) | Expression::Var(_) => (polarity, polarity),
+        Expression::Neg(_) => (polarity.flip(), polarity.flip()),
+        Expression::And(_, _) | Expression::Or(_, _) => (polarity, polarity),
+        Expression::Implication(_, _) => (polarity.flip(), polarity),
+        Expression::Xor(_, _) | Expression::Equivalence(_, _) => (Polarity::Both, Polarity::Both),
+    }
+}
@@ -0,0 +1,241 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::cube::{cover_to_expression, Cube};
+use crate::espresso::{complement, cube_covers, espresso};
+use crate::program::MAX_VARIABLES;
+use crate::variable::{default_names, is_variable_name};
+use crate::{Expression, LogicError, Variable};
+
+// A single-output function in the Berkeley PLA format read and written by Espresso:
+//
+//   .i 3            input count
+//   .o 1            output count, only 1 is supported
+//   .ilb A B C      input names, optional
+//   .ob f           output name, optional
+//   .type fd        meaning of the output column, optional
+//   1-0 1           a cube and its output, one per line
+//   .e
+//
+// Inputs are '1', '0' or '-' (also '2') for a variable left out of the cube.
+// Depending on .type, an output of '1' adds the cube to the on-set, '0' to the
+// off-set and '-' (also '~', '2') to the don't care set. Types f and fd make
+// every other row false, fr and fdr leave the unlisted rows as don't cares.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pla {
+    variables: Vec<Variable>,
+    output: String,
+    on: Vec<Cube>,
+    dont_care: Vec<Cube>,
+    off: Vec<Cube>,
+}
+
+// Which sets the lines of a PLA file describe
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlaType {
+    F,
+    Fd,
+    Fr,
+    Fdr,
+}
+
+impl Pla {
+    pub fn from_expression(expr: &Expression) -> Result<Self, LogicError> {
+        let variables = expr.sorted_variables();
+        let (on, off) = cube_covers(expr, &variables)?;
+        Ok(Pla { variables, output: "f".to_string(), on, dont_care: Vec::new(), off })
+    }
+
+    pub fn variables(&self) -> &[Variable] {
+        &self.variables
+    }
+
+    pub fn on(&self) -> &[Cube] {
+        &self.on
+    }
+
+    pub fn dont_care(&self) -> &[Cube] {
+        &self.dont_care
+    }
+
+    pub fn off(&self) -> &[Cube] {
+        &self.off
+    }
+
+    // Same function with an on-set cover shrunk by espresso
+    pub fn minimize(&self) -> Pla {
+        let on = espresso(self.variables.len(), &self.on, &self.dont_care, &self.off);
+        Pla { on, ..self.clone() }
+    }
+
+    // Sum of products of the on-set, don't cares read as false
+    pub fn to_expression(&self) -> Expression {
+        cover_to_expression(&self.on, &self.variables)
+    }
+}
+
+impl FromStr for Pla {
+    type Err = LogicError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let malformed = |line: usize, message: &str| LogicError::Malformed { line, message: message.to_string() };
+
+        let mut input_count: Option<usize> = None;
+        let mut names: Option<(Vec<Variable>, usize)> = None;
+        let mut output = "f".to_string();
+        let mut kind = PlaType::Fd;
+        let mut expected_cubes: Option<(usize, usize)> = None;
+        let mut cubes: Vec<(Cube, char)> = Vec::new();
+        let mut last_line = 0;
+
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            last_line = line_number;
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut words = line.split_whitespace();
+            let keyword = words.next().unwrap_or("");
+            let arguments: Vec<&str> = words.collect();
+            let number = |arguments: &[&str]| -> Result<usize, LogicError> {
+                match arguments {
+                    [value] => value.parse().map_err(|_| malformed(line_number, "expected a number")),
+                    _ => Err(malformed(line_number, "expected a single number")),
+                }
+            };
+
+            match keyword {
+                ".i" => {
+                    let count = number(&arguments)?;
+                    if count > MAX_VARIABLES {
+                        return Err(LogicError::TooManyVariables { count, max: MAX_VARIABLES });
+                    }
+                    input_count = Some(count);
+                },
+                ".o" => {
+                    if number(&arguments)? != 1 {
+                        return Err(malformed(line_number, "only single-output PLAs are supported"));
+                    }
+                },
+                ".ilb" => {
+                    if let Some(name) = arguments.iter().find(|name| !is_variable_name(name)) {
+                        return Err(malformed(line_number, &format!("'{}' is not a variable name", name)));
+                    }
+                    names = Some((arguments.iter().map(|name| Variable::new(name)).collect(), line_number));
+                },
+                ".ob" => match arguments[..] {
+                    [name] => output = name.to_string(),
+                    _ => return Err(malformed(line_number, "expected a single output name")),
+                },
+                ".p" => expected_cubes = Some((number(&arguments)?, line_number)),
+                ".type" => {
+                    kind = match arguments[..] {
+                        ["f"] => PlaType::F,
+                        ["fd"] => PlaType::Fd,
+                        ["fr"] => PlaType::Fr,
+                        ["fdr"] => PlaType::Fdr,
+                        _ => return Err(malformed(line_number, "expected a type among f, fd, fr and fdr")),
+                    }
+                },
+                ".e" | ".end" => break,
+                _ if keyword.starts_with('.') => {
+                    return Err(malformed(line_number, &format!("unsupported directive '{}'", keyword)));
+                },
+                _ => {
+                    let n = input_count.ok_or_else(|| malformed(line_number, "cube before the .i directive"))?;
+                    let tokens: Vec<&str> = line.split_whitespace().collect();
+                    let (outputs, inputs) = tokens.split_last().unwrap(); // The line is not empty
+                    let inputs: String = inputs.concat();
+                    if inputs.chars().count() != n {
+                        return Err(malformed(line_number, &format!("expected {} inputs, found {}", n, inputs.chars().count())));
+                    }
+
+                    let mut cube = Cube::UNIVERSE;
+                    for (i, c) in inputs.chars().enumerate() {
+                        let bit = 1 << (n - 1 - i);
+                        cube = match c {
+                            '1' => Cube::new(cube.mask | bit, cube.value | bit),
+                            '0' => Cube::new(cube.mask | bit, cube.value),
+                            '-' | '2' => cube,
+                            _ => return Err(malformed(line_number, &format!("unexpected input '{}'", c))),
+                        };
+                    }
+                    let outputs: Vec<char> = outputs.chars().collect();
+                    match outputs[..] {
+                        [c @ ('1' | '0' | '-' | '~' | '2')] => cubes.push((cube, c)),
+                        _ => return Err(malformed(line_number, "expected a single output of 1, 0 or -")),
+                    }
+                },
+            }
+        }
+
+        let n = input_count.ok_or_else(|| malformed(last_line.max(1), "missing .i directive"))?;
+        let variables = match names {
+            Some((names, line)) if names.len() != n => {
+                return Err(malformed(line, &format!("expected {} input names, found {}", n, names.len())));
+            },
+            Some((names, _)) => names,
+            None => default_names(n, 0),
+        };
+        if let Some((count, line)) = expected_cubes.filter(|&(count, _)| count != cubes.len()) {
+            return Err(malformed(line, &format!(".p announces {} cubes, found {}", count, cubes.len())));
+        }
+
+        let select = |outputs: &[char]| -> Vec<Cube> {
+            cubes.iter().filter(|(_, c)| outputs.contains(c)).map(|(cube, _)| *cube).collect()
+        };
+        let on = select(&['1']);
+        let (dont_care, off) = match kind {
+            PlaType::F => (Vec::new(), complement(n, &on)),
+            PlaType::Fd => {
+                let dont_care = select(&['-', '~', '2']);
+                let off = complement(n, &[on.clone(), dont_care.clone()].concat());
+                (dont_care, off)
+            },
+            // Rows listed nowhere are don't cares
+            PlaType::Fr | PlaType::Fdr => {
+                let off = select(&['0']);
+                let mut dont_care = if kind == PlaType::Fdr { select(&['-', '~', '2']) } else { Vec::new() };
+                dont_care.extend(complement(n, &[on.clone(), dont_care.clone(), off.clone()].concat()));
+                (dont_care, off)
+            },
+        };
+        if on.iter().any(|a| off.iter().any(|b| a.intersect(b).is_some())) {
+            return Err(malformed(last_line, "the on-set and the off-set overlap"));
+        }
+
+        Ok(Pla { variables, output, on, dont_care, off })
+    }
+}
+
+// The on-set, then the don't cares, in the default fd type
+impl fmt::Display for Pla {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let n = self.variables.len();
+        let names: Vec<String> = self.variables.iter().map(Variable::to_string).collect();
+
+        writeln!(f, ".i {}", n)?;
+        writeln!(f, ".o 1")?;
+        writeln!(f, ".ilb {}", names.join(" "))?;
+        writeln!(f, ".ob {}", self.output)?;
+        writeln!(f, ".p {}", self.on.len() + self.dont_care.len())?;
+        for (cubes, output) in [(&self.on, '1'), (&self.dont_care, '-')] {
+            for cube in cubes {
+                let inputs: String = (0..n)
+                    .map(|i| {
+                        let bit = 1 << (n - 1 - i);
+                        match (cube.mask & bit != 0, cube.value & bit != 0) {
+                            (false, _) => '-',
+                            (true, true) => '1',
+                            (true, false) => '0',
+                        }
+                    })
+                    .collect();
+                writeln!(f, "{} {}", inputs, output)?;
+            }
+        }
+        writeln!(f, ".e")
+    }
+}
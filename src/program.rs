@@ -0,0 +1,156 @@
+use crate::{Expression, LogicError, Variable};
+
+// Largest number of variables a program can address: one bit each in a u64 row
+pub const MAX_VARIABLES: usize = 64;
+
+// Postfix instructions of the evaluation VM.
+// Binary instructions pop `b` (top of the stack) then `a`, and push `a op b`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Const(bool),
+    // Push the value of the variable at this index of `Program::variables`
+    Load(u8),
+    Not,
+    And,
+    Or,
+    Xor,
+    // a ⇒ b
+    Implication,
+    // a ⇐ b, emitted when the operands of an implication were swapped
+    ConverseImplication,
+    Equivalence,
+}
+
+// An expression compiled to a flat instruction array.
+//
+// Rows are u64 words in truth table order: with n variables, variable i
+// reads bit n - 1 - i, so the first variable is the most significant one
+// and row r of a truth table is simply `evaluate(r)`.
+//
+// The stack lives in a single u64 register, one bit per entry. Operands are
+// emitted deepest subtree first (Sethi-Ullman order), which bounds the stack
+// depth by log2(leaves) + 1, far below 64 for any tree that fits in memory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Program {
+    variables: Vec<Variable>,
+    instructions: Vec<Instruction>,
+}
+
+impl Program {
+    // Compiles over the sorted variables of the expression
+    pub fn compile(expr: &Expression) -> Result<Self, LogicError> {
+        Program::compile_with_variables(expr, &expr.sorted_variables())
+    }
+
+    // Compiles with an explicit variable order, which may include variables
+    // the expression does not use, e.g. to compare two expressions row by row
+    pub fn compile_with_variables(expr: &Expression, variables: &[Variable]) -> Result<Self, LogicError> {
+        if variables.len() > MAX_VARIABLES {
+            return Err(LogicError::TooManyVariables { count: variables.len(), max: MAX_VARIABLES });
+        }
+
+        let mut program = Program { variables: variables.to_vec(), instructions: Vec::new() };
+        program.emit(expr)?;
+        Ok(program)
+    }
+
+    pub fn variables(&self) -> &[Variable] {
+        &self.variables
+    }
+
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    // Number of rows of the truth table, 2^n
+    pub fn row_count(&self) -> u128 {
+        1 << self.variables.len()
+    }
+
+    // m = instruction count
+    // Space complexity: O(1)
+    // Time complexity: O(m)
+    pub fn evaluate(&self, row: u64) -> bool {
+        let last = self.variables.len() as u32;
+        let mut stack: u64 = 0;
+
+        for instruction in &self.instructions {
+            stack = match *instruction {
+                Instruction::Const(value) => (stack << 1) | value as u64,
+                Instruction::Load(index) => (stack << 1) | ((row >> (last - 1 - index as u32)) & 1),
+                Instruction::Not => stack ^ 1,
+                binary => {
+                    let b = stack & 1;
+                    let rest = stack >> 1;
+                    let a = rest & 1;
+                    let result = match binary {
+                        Instruction::And => a & b,
+                        Instruction::Or => a | b,
+                        Instruction::Xor => a ^ b,
+                        Instruction::Implication => (a ^ 1) | b,
+                        Instruction::ConverseImplication => a | (b ^ 1),
+                        _ => (a ^ b) ^ 1,
+                    };
+                    (rest & !1) | result
+                },
+            };
+        }
+
+        stack & 1 == 1
+    }
+
+    fn emit(&mut self, expr: &Expression) -> Result<(), LogicError> {
+        match expr {
+            Expression::Val(value) => self.instructions.push(Instruction::Const(*value)),
+            Expression::Var(v) => {
+                let index = self
+                    .variables
+                    .iter()
+                    .position(|known| known == v)
+                    .ok_or_else(|| LogicError::UnassignedVariable { name: v.to_string() })?;
+                self.instructions.push(Instruction::Load(index as u8));
+            },
+            Expression::Neg(child) => {
+                self.emit(child)?;
+                self.instructions.push(Instruction::Not);
+            },
+            Expression::And(left, right) |
+            Expression::Or(left, right) |
+            Expression::Xor(left, right) |
+            Expression::Implication(left, right) |
+            Expression::Equivalence(left, right) => {
+                // Evaluate the subtree needing the deeper stack first
+                let swap = stack_need(right) > stack_need(left);
+                let (first, second) = if swap { (right, left) } else { (left, right) };
+                self.emit(first)?;
+                self.emit(second)?;
+
+                self.instructions.push(match expr {
+                    Expression::And(_, _) => Instruction::And,
+                    Expression::Or(_, _) => Instruction::Or,
+                    Expression::Xor(_, _) => Instruction::Xor,
+                    Expression::Implication(_, _) if swap => Instruction::ConverseImplication,
+                    Expression::Implication(_, _) => Instruction::Implication,
+                    _ => Instruction::Equivalence,
+                });
+            },
+        }
+        Ok(())
+    }
+}
+
+// Sethi-Ullman number: stack entries needed to evaluate `expr`
+fn stack_need(expr: &Expression) -> usize {
+    match expr {
+        Expression::Val(_) | Expression::Var(_) => 1,
+        Expression::Neg(child) => stack_need(child),
+        Expression::And(left, right) |
+        Expression::Or(left, right) |
+        Expression::Xor(left, right) |
+        Expression::Implication(left, right) |
+        Expression::Equivalence(left, right) => {
+            let (left, right) = (stack_need(left), stack_need(right));
+            if left == right { left + 1 } else { std::cmp::max(left, right) }
+        },
+    }
+}
@@ -1,37 +1,169 @@
-use std::collections::HashSet;
-
-use crate::Expression;
+use crate::cdcl::Cdcl;
+use crate::cnf::Literal;
+use crate::incremental::IncrementalEvaluator;
+use crate::{Assignment, Expression, LogicError, Program, Variable};
 
 // m = formula length
 // v = variable count
-// Space complexity: O(m + v)
-// Time complexity: O(2^v (v + m))
+// Space complexity: O(m + v) - expression tree and its Tseitin encoding
+// Time complexity: O(2^v . m) in the worst case
+//   Cdcl on the linear-size Tseitin CNF of the formula, which only explores
+//   a fraction of the assignments for most formulas
 pub fn sat(formula: &str) -> bool {
-    let expression = match Expression::from_formula(formula) {
+    match try_sat(formula) {
         Ok(value) => value,
         Err(err) => {
-            eprintln!("Error occurred while evaluating: {err:?}");
-            return false;
+            eprintln!("{}", err.render(formula));
+            false
+        }
+    }
+}
+
+pub fn try_sat(formula: &str) -> Result<bool, LogicError> {
+    Ok(sat_expression(&Expression::from_formula(formula)?))
+}
+
+pub fn sat_expression(expression: &Expression) -> bool {
+    solve(expression).is_some()
+}
+
+// A model of the expression: a value for each of its variables making it true.
+// The values of the Tseitin auxiliary variables are dropped.
+pub fn solve(expression: &Expression) -> Option<Assignment> {
+    let tseitin = expression.to_tseitin();
+    let values = Cdcl::new(tseitin.cnf()).solve()?;
+
+    let mut model = Assignment::new();
+    for (var, &value) in tseitin.original_variables().iter().zip(&values) {
+        model.set(var.clone(), value);
+    }
+    debug_assert!(is_model(expression, &model));
+    Some(model)
+}
+
+// Lazy enumeration of the models of an expression projected onto some
+// variables: each projected assignment that extends to a model is returned
+// once. After each model a blocking clause, the negation of its projected
+// values, is added to the solver, which then looks for a different one.
+// Projected variables the expression does not use are free and take both values.
+// k = model count, p = projected variable count
+// Time complexity: k + 1 calls to Cdcl::solve, each keeping what it learned
+pub struct Models {
+    solver: Cdcl,
+    // Projected variables and their index in the CNF
+    projection: Vec<(Variable, usize)>,
+    remaining: Option<usize>,
+    done: bool,
+}
+
+// Every model over the variables of the expression
+pub fn all_models(expression: &Expression) -> Models {
+    all_models_projected(expression, &expression.sorted_variables())
+}
+
+pub fn all_models_projected(expression: &Expression, projection: &[Variable]) -> Models {
+    let mut cnf = expression.to_tseitin().into_cnf();
+    let mut projected: Vec<(Variable, usize)> = Vec::new();
+    for var in projection {
+        if projected.iter().any(|(known, _)| known == var) {
+            continue;
         }
-    };
+        let index = match cnf.variable_index(var) {
+            Some(index) => index,
+            None => cnf.add_variable(var.clone()),
+        };
+        projected.push((var.clone(), index));
+    }
+    Models { solver: Cdcl::new(&cnf), projection: projected, remaining: None, done: false }
+}
+
+impl Models {
+    // Stops after `limit` models
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.remaining = Some(limit);
+        self
+    }
 
-    let vars_set = expression.variables();
-    let vars: Vec<char> = vars_set.into_iter().collect();
+    pub fn projection(&self) -> impl Iterator<Item = &Variable> {
+        self.projection.iter().map(|(var, _)| var)
+    }
+}
 
-    let mut values = HashSet::with_capacity(vars.len());
-    let combination_count = 1 << vars.len();
-    for i in 0..combination_count {
-        values.clear();
+impl Iterator for Models {
+    type Item = Assignment;
 
-        for (j, &var) in vars.iter().enumerate() {
-            if i & (1 << (vars.len() - 1 - j)) != 0 {
-                values.insert(var);
-            }
+    fn next(&mut self) -> Option<Assignment> {
+        if self.done || self.remaining == Some(0) {
+            return None;
         }
+        let Some(values) = self.solver.solve() else {
+            self.done = true;
+            return None;
+        };
 
-        if expression.evaluate(&values) {
-            return true;
+        let mut model = Assignment::new();
+        let mut blocking = Vec::with_capacity(self.projection.len());
+        for (var, index) in &self.projection {
+            model.set(var.clone(), values[*index]);
+            blocking.push(Literal::new(*index, values[*index]));
         }
+        self.solver.add_clause(blocking);
+        self.remaining = self.remaining.map(|remaining| remaining - 1);
+        Some(model)
     }
-    false
+}
+
+// The assignment gives every variable a value and makes the expression true
+pub fn is_model(expression: &Expression, assignment: &Assignment) -> bool {
+    expression.evaluate(assignment) == Ok(true)
+}
+
+// Tries every row, the reference the solvers are checked against
+// Space complexity: O(m + v) - expression tree and compiled program
+// Time complexity: O(2^v . m)
+//   Every row runs the m instructions of the program on a u64 word
+pub fn sat_brute_force(formula: &str) -> bool {
+    match try_sat_brute_force(formula) {
+        Ok(value) => value,
+        Err(err) => {
+            eprintln!("{}", err.render(formula));
+            false
+        }
+    }
+}
+
+pub fn try_sat_brute_force(formula: &str) -> Result<bool, LogicError> {
+    sat_expression_brute_force(&Expression::from_formula(formula)?)
+}
+
+pub fn sat_expression_brute_force(expression: &Expression) -> Result<bool, LogicError> {
+    let program = Program::compile(expression)?;
+
+    for row in 0..program.row_count() {
+        if program.evaluate(row as u64) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+// Same search walking the rows in Gray order, where each row flips a single
+// variable and only re-evaluates the subtrees that depend on it
+// Time complexity: O(2^v . l . h) - l leaves of the flipped variable, h tree height
+pub fn sat_gray(formula: &str) -> bool {
+    match try_sat_gray(formula) {
+        Ok(value) => value,
+        Err(err) => {
+            eprintln!("{}", err.render(formula));
+            false
+        }
+    }
+}
+
+pub fn try_sat_gray(formula: &str) -> Result<bool, LogicError> {
+    let expression = Expression::from_formula(formula)?;
+    let evaluator = IncrementalEvaluator::new(&expression)?;
+
+    let mut rows = evaluator.gray_rows();
+    Ok(rows.any(|(_, value)| value))
 }
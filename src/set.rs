@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use crate::{pop_from_stack, LogicError};
+use crate::{pop_operand, pop_operands, pop_result, LogicError};
 
 // A powerset of S is the set of all subsets of S
 // including the empty set and S.
@@ -93,13 +93,13 @@ pub fn eval_set(formula: &str, sets: Vec<Vec<i32>>) -> Vec<i32> {
     match try_eval_set(formula, sets) {
         Ok(value) => value,
         Err(err) => {
-            eprintln!("Error occurred while evaluating: {err:?}");
+            eprintln!("{}", err.render(formula));
             Vec::new()
         }
     }
 }
 
-fn try_eval_set(formula: &str, sets: Vec<Vec<i32>>) -> Result<Vec<i32>, LogicError> {
+pub fn try_eval_set(formula: &str, sets: Vec<Vec<i32>>) -> Result<Vec<i32>, LogicError> {
     let mut stack: Vec<Vec<i32>> = Vec::with_capacity(formula.len());
 
     // The globally encompassing set is considered to be the union of all
@@ -109,57 +109,47 @@ fn try_eval_set(formula: &str, sets: Vec<Vec<i32>>) -> Result<Vec<i32>, LogicErr
         all = union(all, set.to_vec());
     }
 
-    for character in formula.chars() {
+    for (position, character) in formula.char_indices() {
         match character {
             'A'..='Z' => {
                 let index = character as usize - 'A' as usize;
-                let set = sets.get(index).ok_or(LogicError::UnknownVar)?;
+                let set = sets.get(index).ok_or(LogicError::UnknownVar { position, symbol: character })?;
                 stack.push(set.clone());
             }
 
             // Negation
             '!' => {
-                let val = pop_from_stack(&mut stack)?;
+                let val = pop_operand(&mut stack, position, character)?;
                 stack.push(complement(all.clone(), val));
             },
 
             // Binary operators
             '&' => {
-                let right = pop_from_stack(&mut stack)?;
-                let left = pop_from_stack(&mut stack)?;
+                let (left, right) = pop_operands(&mut stack, position, character)?;
                 stack.push(intersection(right, left));
             },
             '|' => {
-                let right = pop_from_stack(&mut stack)?;
-                let left = pop_from_stack(&mut stack)?;
+                let (left, right) = pop_operands(&mut stack, position, character)?;
                 stack.push(union(right, left));
             },
             '^' => {
-                let right = pop_from_stack(&mut stack)?;
-                let left = pop_from_stack(&mut stack)?;
+                let (left, right) = pop_operands(&mut stack, position, character)?;
                 stack.push(xunion(right, left));
             },
             '>' => {
-                let right = pop_from_stack(&mut stack)?;
-                let left = pop_from_stack(&mut stack)?;
+                let (left, right) = pop_operands(&mut stack, position, character)?;
                 stack.push(complement(all.clone(), complement(right, left)))
             },
             '=' => {
-                let right = pop_from_stack(&mut stack)?;
-                let left = pop_from_stack(&mut stack)?;
+                let (left, right) = pop_operands(&mut stack, position, character)?;
                 stack.push(complement(all.clone(), intersection(left, right)));
             },
 
             // Unrecognized character
-            _ => return Err(LogicError::UnrecognizedSymbol),
+            _ => return Err(LogicError::UnrecognizedSymbol { position, symbol: character }),
         }
     }
 
-    if stack.len() != 1 {
-        return Err(LogicError::IncompleteFormula);
-    }
-
-    // Safe because we just checked stack.len() == 1
-    Ok(stack.pop().unwrap()) 
+    pop_result(&mut stack, formula)
 }
 
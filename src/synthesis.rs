@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+
+use crate::bitslice::MAX_BITSLICED_VARIABLES;
+use crate::cube::{cover_to_expression, Cube};
+use crate::truth_table::TruthTable;
+use crate::variable::is_variable_name;
+use crate::{Expression, LogicError, TruthColumn, Variable};
+
+// Rows use the truth table convention: with n variables, variable i is
+// bit n - 1 - i of the row, so minterm 5 of (A, B, C) is A & !B & C.
+
+// Canonical sum of products: one full conjunction of literals per minterm.
+// v = variable count, k = minterm count
+// Space complexity: O(k . v)
+// Time complexity: O(k . v)
+pub fn sum_of_products(variables: &[Variable], minterms: &[u64]) -> Expression {
+    let cover: Vec<Cube> = minterms.iter().map(|&row| Cube::minterm(row, variables.len())).collect();
+    cover_to_expression(&cover, variables)
+}
+
+// Canonical product of sums: one full disjunction of literals per maxterm,
+// false exactly on that row.
+// v = variable count, k = maxterm count
+// Space complexity: O(k . v)
+// Time complexity: O(k . v)
+pub fn product_of_sums(variables: &[Variable], maxterms: &[u64]) -> Expression {
+    let clauses = maxterms.iter().map(|&row| Cube::minterm(row, variables.len()).to_clause(variables));
+    right_fold(clauses.collect(), Expression::And, true)
+}
+
+// Right-associative tree, like flatten_expression builds: A & (B & C).
+// An empty list is the identity of the operator.
+pub(crate) fn right_fold(
+    mut operands: Vec<Expression>,
+    constructor: fn(Box<Expression>, Box<Expression>) -> Expression,
+    identity: bool,
+) -> Expression {
+    let Some(mut result) = operands.pop() else {
+        return Expression::val(identity);
+    };
+    while let Some(operand) = operands.pop() {
+        result = constructor(Box::new(operand), Box::new(result));
+    }
+    result
+}
+
+impl TruthTable {
+    // Table true exactly on the given rows
+    pub fn from_minterms(variables: Vec<Variable>, minterms: &[u64]) -> Result<Self, LogicError> {
+        if variables.len() > MAX_BITSLICED_VARIABLES {
+            return Err(LogicError::TooManyVariables { count: variables.len(), max: MAX_BITSLICED_VARIABLES });
+        }
+
+        let mut output = TruthColumn::from_words(Vec::new(), variables.len());
+        for &row in minterms {
+            if row >= output.len() {
+                return Err(LogicError::InvalidRow { row, row_count: output.len() });
+            }
+            output.set(row, true);
+        }
+        Ok(TruthTable::from_column(variables, output))
+    }
+
+    // Table false exactly on the given rows
+    pub fn from_maxterms(variables: Vec<Variable>, maxterms: &[u64]) -> Result<Self, LogicError> {
+        let table = TruthTable::from_minterms(variables, maxterms)?;
+        let words = table.output().words().iter().map(|word| !word).collect();
+        let output = TruthColumn::from_words(words, table.variables().len());
+        Ok(TruthTable::from_column(table.variables().to_vec(), output))
+    }
+
+    // Reads a table as print_truth_table prints it.
+    // Leading columns titled with a variable name are the inputs, the last
+    // column is the output and anything in between, such as the intermediate
+    // columns of from_expression_with_steps, is ignored. Rows may come in any
+    // order but every assignment must appear exactly once. An output of '-'
+    // marks a don't care row.
+    pub fn from_markdown(markdown: &str) -> Result<Self, LogicError> {
+        let mut lines = markdown
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        let malformed = |line: usize, message: &str| LogicError::Malformed { line, message: message.to_string() };
+
+        let (header_line, header) = lines.next().ok_or_else(|| malformed(1, "missing header row"))?;
+        let headers = split_markdown_row(header).ok_or_else(|| malformed(header_line, "expected a row starting and ending with '|'"))?;
+
+        // The last column is the output even if titled like a variable
+        let variables: Vec<Variable> = headers[..headers.len() - 1]
+            .iter()
+            .take_while(|name| is_variable_name(name))
+            .map(|name| Variable::new(name))
+            .collect();
+        if variables.len() > MAX_BITSLICED_VARIABLES {
+            return Err(LogicError::TooManyVariables { count: variables.len(), max: MAX_BITSLICED_VARIABLES });
+        }
+        let unique: HashSet<&Variable> = variables.iter().collect();
+        if unique.len() != variables.len() {
+            return Err(malformed(header_line, "duplicate variable column"));
+        }
+
+        let mut output = TruthColumn::from_words(Vec::new(), variables.len());
+        let mut dont_care = output.clone();
+        let mut seen = HashSet::new();
+        for (line_number, line) in lines {
+            let cells = split_markdown_row(line).ok_or_else(|| malformed(line_number, "expected a row starting and ending with '|'"))?;
+
+            // The |---|---| separator
+            if cells.iter().all(|cell| !cell.is_empty() && cell.chars().all(|c| c == '-' || c == ':')) {
+                continue;
+            }
+            if cells.len() != headers.len() {
+                return Err(malformed(line_number, &format!("expected {} cells, found {}", headers.len(), cells.len())));
+            }
+
+            let mut row = 0;
+            for cell in &cells[..variables.len()] {
+                row = (row << 1) | parse_bit(cell).ok_or_else(|| malformed(line_number, "input cells must be 0 or 1"))? as u64;
+            }
+            if !seen.insert(row) {
+                return Err(malformed(line_number, "duplicate row"));
+            }
+            match cells[cells.len() - 1].as_str() {
+                "-" => dont_care.set(row, true),
+                cell => {
+                    let value = parse_bit(cell).ok_or_else(|| malformed(line_number, "output cells must be 0, 1 or -"))?;
+                    output.set(row, value);
+                },
+            }
+        }
+
+        if seen.len() as u64 != output.len() {
+            let message = format!("expected {} rows, found {}", output.len(), seen.len());
+            return Err(malformed(markdown.lines().count(), &message));
+        }
+        let table = TruthTable::from_column(variables, output);
+        if dont_care.count_ones() == 0 {
+            return Ok(table);
+        }
+        Ok(table.with_dont_care(dont_care))
+    }
+
+    // Rows where the output is 1, except don't cares
+    pub fn minterms(&self) -> Vec<u64> {
+        self.rows().filter(|row| row.output && !row.dont_care).map(|row| row.index).collect()
+    }
+
+    // Rows where the output is 0, except don't cares
+    pub fn maxterms(&self) -> Vec<u64> {
+        self.rows().filter(|row| !row.output && !row.dont_care).map(|row| row.index).collect()
+    }
+
+    pub fn dont_cares(&self) -> Vec<u64> {
+        self.rows().filter(|row| row.dont_care).map(|row| row.index).collect()
+    }
+
+    // Don't cares are false in the canonical DNF and true in the canonical CNF
+    pub fn to_canonical_dnf(&self) -> Expression {
+        sum_of_products(self.variables(), &self.minterms())
+    }
+
+    pub fn to_canonical_cnf(&self) -> Expression {
+        product_of_sums(self.variables(), &self.maxterms())
+    }
+}
+
+// "\|" inside a cell, as in the label of an or step, is a literal '|'
+fn split_markdown_row(line: &str) -> Option<Vec<String>> {
+    let inner = line.strip_prefix('|')?;
+    if !inner.ends_with('|') || inner.ends_with("\\|") {
+        return None;
+    }
+
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => cell.push(chars.next()?),
+            '|' => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            c => cell.push(c),
+        }
+    }
+    Some(cells)
+}
+
+fn parse_bit(cell: &str) -> Option<bool> {
+    match cell {
+        "1" => Some(true),
+        "0" => Some(false),
+        _ => None,
+    }
+}
@@ -1,57 +1,456 @@
-use std::collections::HashSet;
-
-use crate::Expression;
+use crate::bitslice::{block_count, ROWS_PER_BLOCK};
+use crate::expression::{NodeIds, NodeKey};
+use crate::incremental::IncrementalEvaluator;
+use crate::{gray_code, Expression, LogicError, Program, TruthColumn, Variable};
 
+// Rows are printed as they are computed, the table is never held in memory
 // m = formula length
 // v = variable count
 // Space complexity: O(m + v)
-// Time complexity: O(2^v . (v + m))
+// Time complexity: O(2^v . v + 2^v / 64 . m)
 //   We generate 2^v rows - ex 2 variables -> 2^2 = 4 rows
-//   For each row: determine variables O(v) & evaluate which is O(m)
+//   The output is evaluated 64 rows at a time, each row prints v values
 pub fn print_truth_table(formula: &str) {
-    let expression = match Expression::from_formula(formula) {
-        Ok(value) => value,
-        Err(err) => {
-            eprintln!("Error occurred while evaluating: {err:?}");
-            return;
-        },
-    };
+    print_truth_table_ordered(formula, RowOrder::Binary);
+}
 
-    let vars_set = expression.variables();
-    let mut vars: Vec<char> = vars_set.into_iter().collect();
-    vars.sort();
+pub fn try_print_truth_table(formula: &str) -> Result<(), LogicError> {
+    try_print_truth_table_ordered(formula, RowOrder::Binary)
+}
 
-    print_table_header(&vars);
+// Order in which the rows of a truth table are listed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RowOrder {
+    // Counting in binary: 000, 001, 010, 011, ...
+    #[default]
+    Binary,
+    // Successive rows differ by a single variable: 000, 001, 011, 010, ...
+    Gray,
+}
 
-    let mut values = HashSet::with_capacity(vars.len());
+pub fn print_truth_table_ordered(formula: &str, order: RowOrder) {
+    if let Err(err) = try_print_truth_table_ordered(formula, order) {
+        eprintln!("{}", err.render(formula));
+    }
+}
 
-    let row_count = 1 << vars.len();
-    for row in 0..row_count {
-        values.clear();
+// In Gray order each row only re-evaluates the subtrees depending on the
+// variable that changed, see IncrementalEvaluator
+pub fn try_print_truth_table_ordered(formula: &str, order: RowOrder) -> Result<(), LogicError> {
+    let expression = Expression::from_formula(formula)?;
 
-        // Build the combination
-        for (col, &var) in vars.iter().enumerate() {
-            if row & (1 << (vars.len() - 1 - col)) != 0 {
-                values.insert(var);
+    match order {
+        RowOrder::Binary => {
+            let program = Program::compile(&expression)?;
+            let headers = table_headers(program.variables());
+            print!("{}", markdown_header(&headers));
+            // Fewer than 64 rows when there are fewer than 6 variables
+            let rows_per_block = program.row_count().min(ROWS_PER_BLOCK as u128) as u64;
+            for block in 0..block_count(program.variables().len()) {
+                let outputs = program.evaluate_block(block);
+                for offset in 0..rows_per_block {
+                    let output = (outputs >> offset) & 1 == 1;
+                    print!("{}", markdown_row(&headers, block * ROWS_PER_BLOCK + offset, output));
+                }
             }
+        },
+        RowOrder::Gray => {
+            let evaluator = IncrementalEvaluator::new(&expression)?;
+            let headers = table_headers(evaluator.variables());
+            print!("{}", markdown_header(&headers));
+            for (row, output) in evaluator.gray_rows() {
+                print!("{}", markdown_row(&headers, row, output));
+            }
+        },
+    }
+    Ok(())
+}
+
+// Variable names then the output column
+fn table_headers(variables: &[Variable]) -> Vec<String> {
+    let mut headers: Vec<String> = variables.iter().map(Variable::to_string).collect();
+    headers.push("=".to_string());
+    headers
+}
+
+// A row of a table without steps, the inputs read from the bits of `row`
+fn markdown_row(headers: &[String], row: u64, output: bool) -> String {
+    let inputs = headers.len() - 1;
+    let mut line: Vec<&str> = (0..inputs).map(|i| bit((row >> (inputs - 1 - i)) & 1 == 1)).collect();
+    line.push(bit(output));
+    markdown_line(headers, &line)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+    // | A | B | = |, the layout of print_truth_table
+    Markdown,
+    Csv,
+    Html,
+    // A LaTeX tabular environment
+    Latex,
+    // Space aligned columns, the output separated by a bar
+    Text,
+}
+
+// How intermediate columns are titled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepLabel {
+    // A & !B
+    Infix,
+    // AB!&
+    Rpn,
+}
+
+// Column of a subexpression, shown between the inputs and the output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub label: String,
+    pub expression: Expression,
+    pub column: TruthColumn,
+}
+
+// Inputs and output of a formula on every assignment of its variables,
+// optionally with the intermediate results of its subexpressions.
+// Rows follow the convention of Program: with n variables, variable i is
+// bit n - 1 - i of the row index.
+// Rows marked as don't cares are inputs that never occur: their output is
+// shown as '-' and left out of minterms and maxterms, and two tables with the
+// same don't cares are equal whatever output those rows hold.
+#[derive(Debug, Clone)]
+pub struct TruthTable {
+    variables: Vec<Variable>,
+    steps: Vec<Step>,
+    output: TruthColumn,
+    dont_care: Option<TruthColumn>,
+}
+
+impl PartialEq for TruthTable {
+    fn eq(&self, other: &Self) -> bool {
+        let no_dont_care = vec![0; self.output.words().len()];
+        let left = self.dont_care.as_ref().map_or(&no_dont_care[..], TruthColumn::words);
+        let right = other.dont_care.as_ref().map_or(&no_dont_care[..], TruthColumn::words);
+
+        self.variables == other.variables
+            && self.steps == other.steps
+            && left == right
+            && self.output
+                .words()
+                .iter()
+                .zip(other.output.words())
+                .zip(left)
+                .all(|((a, b), dont_care)| (a ^ b) & !dont_care == 0)
+    }
+}
+
+impl Eq for TruthTable {}
+
+// One line of a truth table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Row {
+    pub index: u64,
+    pub output: bool,
+    pub dont_care: bool,
+    variable_count: usize,
+}
+
+impl Row {
+    // Value of the variable at `variable` in the table's variables
+    pub fn value(&self, variable: usize) -> bool {
+        (self.index >> (self.variable_count - 1 - variable)) & 1 == 1
+    }
+
+    pub fn values(&self) -> Vec<bool> {
+        (0..self.variable_count).map(|variable| self.value(variable)).collect()
+    }
+}
+
+impl TruthTable {
+    pub fn from_expression(expr: &Expression) -> Result<Self, LogicError> {
+        let program = Program::compile(expr)?;
+        Ok(TruthTable::from_column(program.variables().to_vec(), program.truth_column()?))
+    }
+
+    // Adds one column per distinct operator node of the expression, children
+    // before their parents, so the evaluation can be followed step by step.
+    // The root is the output column and leaves are the inputs, so neither is repeated.
+    // s = distinct subexpressions
+    // Space complexity: O(s . 2^v / 64)
+    // Time complexity: O(s . m . 2^v / 64)
+    pub fn from_expression_with_steps(expr: &Expression, label: StepLabel) -> Result<Self, LogicError> {
+        let mut table = TruthTable::from_expression(expr)?;
+
+        let mut subexpressions = Vec::new();
+        collect_subexpressions(expr, &mut subexpressions, &mut NodeIds::default());
+        subexpressions.pop(); // The root, already the output
+
+        for expression in subexpressions {
+            let program = Program::compile_with_variables(&expression, &table.variables)?;
+            let label = match label {
+                StepLabel::Infix => expression.to_infix(),
+                StepLabel::Rpn => expression.to_rpn(),
+            };
+            table.steps.push(Step { label, column: program.truth_column()?, expression });
+        }
+        Ok(table)
+    }
+
+    pub fn from_formula(formula: &str) -> Result<Self, LogicError> {
+        TruthTable::from_expression(&Expression::from_formula(formula)?)
+    }
+
+    // `output` must have one row per assignment of `variables`
+    pub fn from_column(variables: Vec<Variable>, output: TruthColumn) -> Self {
+        assert_eq!(variables.len(), output.variable_count(), "one output row per assignment");
+        TruthTable { variables, output, steps: Vec::new(), dont_care: None }
+    }
+
+    // Marks the rows set in `dont_care`, which must have one row per assignment
+    pub fn with_dont_care(mut self, dont_care: TruthColumn) -> Self {
+        assert_eq!(self.variables.len(), dont_care.variable_count(), "one don't care row per assignment");
+        self.dont_care = Some(dont_care);
+        self
+    }
+
+    pub fn dont_care(&self) -> Option<&TruthColumn> {
+        self.dont_care.as_ref()
+    }
+
+    pub fn is_dont_care(&self, index: u64) -> bool {
+        self.dont_care.as_ref().is_some_and(|column| column.get(index))
+    }
+
+    pub fn variables(&self) -> &[Variable] {
+        &self.variables
+    }
+
+    pub fn output(&self) -> &TruthColumn {
+        &self.output
+    }
+
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    pub fn row_count(&self) -> u64 {
+        self.output.len()
+    }
+
+    pub fn row(&self, index: u64) -> Row {
+        Row {
+            index,
+            output: self.output.get(index),
+            dont_care: self.is_dont_care(index),
+            variable_count: self.variables.len(),
         }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = Row> + '_ {
+        self.rows_ordered(RowOrder::Binary)
+    }
+
+    pub fn rows_ordered(&self, order: RowOrder) -> impl Iterator<Item = Row> + '_ {
+        (0..self.row_count()).map(move |step| match order {
+            RowOrder::Binary => self.row(step),
+            RowOrder::Gray => self.row(gray_code(step as u32) as u64),
+        })
+    }
+
+    pub fn render(&self, format: TableFormat) -> String {
+        self.render_ordered(format, RowOrder::Binary)
+    }
+
+    pub fn render_ordered(&self, format: TableFormat, order: RowOrder) -> String {
+        let mut headers: Vec<String> = self.variables.iter().map(Variable::to_string).collect();
+        headers.extend(self.steps.iter().map(|step| step.label.clone()));
+        headers.push("=".to_string());
 
-        let result = expression.evaluate(&values);
+        let cells: Vec<Vec<&str>> = self
+            .rows_ordered(order)
+            .map(|row| {
+                let mut line: Vec<&str> = row.values().into_iter().map(bit).collect();
+                line.extend(self.steps.iter().map(|step| bit(step.column.get(row.index))));
+                line.push(if row.dont_care { "-" } else { bit(row.output) });
+                line
+            })
+            .collect();
 
-        print!("|");
-        for v in &vars {
-            print!(" {} |", if values.contains(v) { "1" } else { "0" });
+        let inputs = self.variables.len();
+        match format {
+            TableFormat::Markdown => render_markdown(&headers, &cells),
+            TableFormat::Csv => render_csv(&headers, &cells),
+            TableFormat::Html => render_html(&headers, &cells),
+            TableFormat::Latex => render_latex(&headers, &cells, inputs),
+            TableFormat::Text => render_text(&headers, &cells, inputs),
         }
-        println!(" {} |", if result { "1" } else { "0" });
+    }
+
+    pub fn to_markdown(&self) -> String {
+        self.render(TableFormat::Markdown)
+    }
+
+    pub fn to_csv(&self) -> String {
+        self.render(TableFormat::Csv)
+    }
+
+    pub fn to_html(&self) -> String {
+        self.render(TableFormat::Html)
+    }
+
+    pub fn to_latex(&self) -> String {
+        self.render(TableFormat::Latex)
+    }
+
+    pub fn to_text(&self) -> String {
+        self.render(TableFormat::Text)
     }
 }
 
-fn print_table_header(vars: &[char]) {
-    print!("|");
-    for v in vars {
-        print!(" {} |", v)
+// Distinct operator nodes in post-order, a node after all of its children.
+// Returns the id of `expr` in `ids`, which tells repeated subtrees apart in O(1).
+fn collect_subexpressions<'a>(expr: &'a Expression, result: &mut Vec<Expression>, ids: &mut NodeIds<'a>) -> usize {
+    let key = NodeKey::new(expr, |child| collect_subexpressions(child, result, ids));
+    let (id, first) = ids.insert(key);
+    if first && !matches!(expr, Expression::Val(_) | Expression::Var(_)) {
+        result.push(expr.clone());
+    }
+    id
+}
+
+fn bit(value: bool) -> &'static str {
+    if value { "1" } else { "0" }
+}
+
+fn width(header: &str) -> usize {
+    header.chars().count()
+}
+
+// Columns are as wide as their header, "| A |" for single letters.
+// A '|' in a step label is written "\|" so it doesn't end the cell.
+fn render_markdown(headers: &[String], cells: &[Vec<&str>]) -> String {
+    let headers: Vec<String> = headers.iter().map(|header| escape_markdown(header)).collect();
+    let mut result = markdown_header(&headers);
+    for line in cells {
+        result.push_str(&markdown_line(&headers, line));
+    }
+    result
+}
+
+// The titles and the rule below them
+fn markdown_header(headers: &[String]) -> String {
+    let mut result = String::from("|");
+    for header in headers {
+        result.push_str(&format!(" {} |", header));
+    }
+    result.push_str("\n|");
+    for header in headers {
+        result.push_str(&format!("-{}-|", "-".repeat(width(header))));
     }
-    println!(" = |");
-    println!("{}|", "|---".repeat(vars.len() + 1));
+    result.push('\n');
+    result
 }
 
+fn markdown_line(headers: &[String], line: &[&str]) -> String {
+    let mut result = String::from("|");
+    for (cell, header) in line.iter().zip(headers) {
+        result.push_str(&format!(" {:^width$} |", cell, width = width(header)));
+    }
+    result.push('\n');
+    result
+}
+
+fn render_csv(headers: &[String], cells: &[Vec<&str>]) -> String {
+    let mut result = headers.join(",");
+    result.push('\n');
+    for line in cells {
+        result.push_str(&line.join(","));
+        result.push('\n');
+    }
+    result
+}
+
+fn render_html(headers: &[String], cells: &[Vec<&str>]) -> String {
+    let mut result = String::from("<table>\n  <thead>\n    <tr>");
+    for header in headers {
+        result.push_str(&format!("<th>{}</th>", escape_html(header)));
+    }
+    result.push_str("</tr>\n  </thead>\n  <tbody>\n");
+    for line in cells {
+        result.push_str("    <tr>");
+        for cell in line {
+            result.push_str(&format!("<td>{}</td>", cell));
+        }
+        result.push_str("</tr>\n");
+    }
+    result.push_str("  </tbody>\n</table>\n");
+    result
+}
+
+// The first `inputs` columns are separated from the results by a double rule
+fn render_latex(headers: &[String], cells: &[Vec<&str>], inputs: usize) -> String {
+    let columns = format!("{}||{}", "c".repeat(inputs), "c".repeat(headers.len() - inputs));
+    let mut result = format!("\\begin{{tabular}}{{{}}}\n", columns);
+
+    let names: Vec<String> = headers.iter().map(|header| escape_latex(header)).collect();
+    result.push_str(&format!("{} \\\\\n\\hline\n", names.join(" & ")));
+    for line in cells {
+        result.push_str(&format!("{} \\\\\n", line.join(" & ")));
+    }
+    result.push_str("\\end{tabular}\n");
+    result
+}
+
+fn render_text(headers: &[String], cells: &[Vec<&str>], inputs: usize) -> String {
+    let format_line = |line: Vec<String>| {
+        let (left, right) = line.split_at(inputs);
+        let mut text = left.join(" ");
+        if !left.is_empty() {
+            text.push_str(" | ");
+        } else {
+            text.push_str("| ");
+        }
+        text.push_str(&right.join(" "));
+        text.push('\n');
+        text
+    };
+
+    let mut result = format_line(headers.to_vec());
+    for line in cells {
+        let padded = line
+            .iter()
+            .zip(headers)
+            .map(|(cell, header)| format!("{:<width$}", cell, width = width(header)))
+            .collect();
+        result.push_str(&format_line(padded));
+    }
+    result
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|")
+}
+
+// Also escapes the text of the SVG Karnaugh maps
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+// Letters are typeset as math, names and subexpressions as plain text
+fn escape_latex(text: &str) -> String {
+    if text.chars().count() == 1 {
+        return format!("${}$", text);
+    }
+
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '&' | '_' | '%' | '#' | '$' => {
+                escaped.push('\\');
+                escaped.push(c);
+            },
+            '^' => escaped.push_str("\\^{}"),
+            _ => escaped.push(c),
+        }
+    }
+    format!("\\texttt{{{}}}", escaped)
+}
@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+
+use crate::cnf::{Clause, Cnf, Literal};
+use crate::expression::{NodeIds, NodeKey};
+use crate::negation_normal_form::{operand_polarities, Polarity};
+use crate::{Expression, Variable};
+
+// Equisatisfiable CNF of an expression in linear size.
+// Every operator node gets a fresh auxiliary variable x constrained to be
+// equivalent to the node, x <=> a & b for instance, which takes at most four
+// clauses of three literals. The root variable is then asserted by a unit clause.
+// Negations need no variable, they negate the literal of their child, and
+// identical subexpressions share one variable.
+// Any model of the CNF restricted to the original variables satisfies the
+// expression, and any model of the expression extends to one of the CNF by
+// giving each auxiliary variable the value of its subexpression.
+// m = size of the expression
+// Space complexity: O(m)
+// Time complexity: O(m) expected, hashing each node once
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tseitin {
+    cnf: Cnf,
+    // Original variables come first in the CNF, auxiliary ones after
+    original_count: usize,
+    expression: Expression,
+    // Pre-order position in `expression` of the subexpression of each
+    // auxiliary variable, in variable order. Positions rather than copies
+    // keep the encoding linear: copies of nested subexpressions add up to
+    // O(m^2) on a chain.
+    definitions: Vec<usize>,
+}
+
+impl Expression {
+    pub fn to_tseitin(&self) -> Tseitin {
+        Encoder::new(self, false).finish()
+    }
+
+    // Plaisted-Greenbaum encoding: a subformula only used positively, once
+    // the formula is in negation normal form, only needs x => subformula, and
+    // one only used negatively subformula => x. About half the clauses of
+    // to_tseitin, still equisatisfiable, but an auxiliary variable may now
+    // differ from its subexpression in a model.
+    pub fn to_plaisted_greenbaum(&self) -> Tseitin {
+        Encoder::new(self, true).finish()
+    }
+}
+
+impl Tseitin {
+    pub fn cnf(&self) -> &Cnf {
+        &self.cnf
+    }
+
+    pub fn into_cnf(self) -> Cnf {
+        self.cnf
+    }
+
+    // Variables of the expression, sorted
+    pub fn original_variables(&self) -> &[Variable] {
+        &self.cnf.variables()[..self.original_count]
+    }
+
+    pub fn auxiliary_variables(&self) -> &[Variable] {
+        &self.cnf.variables()[self.original_count..]
+    }
+
+    // The subexpression an auxiliary variable stands for
+    // Time complexity: O(m)
+    pub fn subexpression(&self, var: &Variable) -> Option<&Expression> {
+        let index = self.auxiliary_variables().iter().position(|aux| aux == var)?;
+        Some(pre_order(&self.expression)[self.definitions[index]])
+    }
+
+    // Auxiliary variables with their subexpressions
+    // Time complexity: O(m)
+    pub fn definitions(&self) -> impl Iterator<Item = (&Variable, &Expression)> {
+        let nodes = pre_order(&self.expression);
+        self.auxiliary_variables().iter().zip(self.definitions.iter().map(move |&position| nodes[position]))
+    }
+}
+
+struct Encoder<'a> {
+    cnf: Cnf,
+    // Index of each variable of the CNF, original and auxiliary
+    indices: HashMap<Variable, usize>,
+    original_count: usize,
+    // Nodes of the expression in pre-order, with the size of their subtree
+    // and an id shared by equal subtrees
+    nodes: Vec<&'a Expression>,
+    sizes: Vec<usize>,
+    ids: Vec<usize>,
+    definitions: Vec<usize>,
+    // Literal of each encoded id, with the polarities already defined
+    cache: Vec<Option<(Literal, Polarity)>>,
+    next_name: usize,
+    // Plaisted-Greenbaum: only emit the directions the polarity needs
+    polarity_aware: bool,
+}
+
+// A node of encode waiting for the literals of its operands
+struct Frame {
+    position: usize,
+    // Directions still to define
+    missing: Polarity,
+    x: Literal,
+    // Whether the node was reached through an odd number of negations
+    negated: bool,
+    operands: Vec<Literal>,
+}
+
+impl<'a> Encoder<'a> {
+    // Numbers the nodes once, children before parents, so equal subtrees are
+    // found from the ids of their children instead of hashing them in full
+    fn new(expr: &'a Expression, polarity_aware: bool) -> Self {
+        let variables = expr.sorted_variables();
+        let nodes = pre_order(expr);
+        let mut sizes = vec![1; nodes.len()];
+        let mut ids = vec![0; nodes.len()];
+        let mut node_ids = NodeIds::default();
+        for position in (0..nodes.len()).rev() {
+            let mut child = position + 1;
+            let key = NodeKey::new(nodes[position], |_| {
+                let id = ids[child];
+                sizes[position] += sizes[child];
+                child += sizes[child];
+                id
+            });
+            ids[position] = node_ids.insert(key).0;
+        }
+
+        Encoder {
+            original_count: variables.len(),
+            indices: variables.iter().cloned().zip(0..).collect(),
+            cnf: Cnf::new(variables),
+            cache: vec![None; nodes.len()],
+            nodes,
+            sizes,
+            ids,
+            definitions: Vec::new(),
+            next_name: 0,
+            polarity_aware,
+        }
+    }
+
+    fn finish(mut self) -> Tseitin {
+        let root = self.encode(0, Polarity::Positive);
+        self.cnf.add_clause(vec![root]);
+        Tseitin {
+            original_count: self.original_count,
+            expression: self.nodes[0].clone(),
+            definitions: self.definitions,
+            cnf: self.cnf,
+        }
+    }
+
+    // Literal standing for the node at `position`, adding the clauses defining it.
+    // With a positive polarity only x => expr is needed: the clauses holding !x.
+    // With a negative one only expr => x: the clauses holding x.
+    // Nodes are defined depth-first, children before their parent, with an
+    // explicit stack so deep expressions do not exhaust the call stack.
+    fn encode(&mut self, position: usize, polarity: Polarity) -> Literal {
+        let mut frames: Vec<Frame> = Vec::new();
+        let mut result = self.enter(position, polarity, &mut frames);
+        while let Some(frame) = frames.last_mut() {
+            if let Some(literal) = result.take() {
+                frame.operands.push(literal);
+            }
+            let expr = self.nodes[frame.position];
+            let arity = if matches!(expr, Expression::Val(_)) { 0 } else { 2 };
+            if frame.operands.len() < arity {
+                let (left, right) = operand_polarities(expr, frame.missing);
+                let left_position = frame.position + 1;
+                let (child, polarity) = match frame.operands.len() {
+                    0 => (left_position, left),
+                    _ => (left_position + self.sizes[left_position], right),
+                };
+                result = self.enter(child, polarity, &mut frames);
+                continue;
+            }
+
+            let frame = frames.pop().unwrap(); // The loop holds one
+            let x = frame.x;
+            for clause in definition(expr, x, &frame.operands) {
+                let positive_direction = clause.contains(&!x);
+                if (positive_direction && frame.missing.has_positive()) || (!positive_direction && frame.missing.has_negative()) {
+                    self.cnf.add_clause(clause);
+                }
+            }
+            result = Some(if frame.negated { !x } else { x });
+        }
+        result.unwrap() // Set by the last frame, or by enter when there was none
+    }
+
+    // Starts encoding the node at `position`: variables, negations and nodes
+    // already defined with `polarity` give their literal at once, the others
+    // get their auxiliary variable and a frame waiting for their operands
+    fn enter(&mut self, mut position: usize, polarity: Polarity, frames: &mut Vec<Frame>) -> Option<Literal> {
+        let mut polarity = if self.polarity_aware { polarity } else { Polarity::Both };
+        let mut negated = false;
+        while let Expression::Neg(_) = self.nodes[position] {
+            position += 1;
+            polarity = polarity.flip();
+            negated = !negated;
+        }
+        let literal = |x: Literal| Some(if negated { !x } else { x });
+        if let Expression::Var(v) = self.nodes[position] {
+            return literal(Literal::positive(self.indices[v])); // Every variable of the expression is known
+        }
+
+        let id = self.ids[position];
+        let (x, done) = match self.cache[id] {
+            Some((x, done)) => (x, Some(done)),
+            None => (self.fresh(position), None),
+        };
+        let missing = match done {
+            None => polarity,
+            Some(done) => match (polarity.has_positive() && !done.has_positive(), polarity.has_negative() && !done.has_negative()) {
+                (false, false) => return literal(x),
+                (true, false) => Polarity::Positive,
+                (false, true) => Polarity::Negative,
+                (true, true) => Polarity::Both,
+            },
+        };
+        let defined = match done {
+            None => missing,
+            Some(_) => Polarity::Both,
+        };
+        self.cache[id] = Some((x, defined));
+
+        frames.push(Frame { position, missing, x, negated, operands: Vec::with_capacity(2) });
+        None
+    }
+
+    // New auxiliary variable _t0, _t1, ... skipping names of the expression
+    fn fresh(&mut self, position: usize) -> Literal {
+        let name = loop {
+            let candidate = Variable::new(&format!("_t{}", self.next_name));
+            self.next_name += 1;
+            if !self.indices.contains_key(&candidate) {
+                break candidate;
+            }
+        };
+        self.definitions.push(position);
+        let index = self.cnf.add_variable(name.clone());
+        self.indices.insert(name, index);
+        Literal::positive(index)
+    }
+}
+
+// Clauses of x <=> expr, given the literals of the operands of expr
+fn definition(expr: &Expression, x: Literal, operands: &[Literal]) -> Vec<Clause> {
+    match (expr, operands) {
+        (Expression::Val(value), []) => vec![vec![if *value { x } else { !x }]],
+        (Expression::And(_, _), &[a, b]) => vec![vec![!x, a], vec![!x, b], vec![x, !a, !b]],
+        (Expression::Or(_, _), &[a, b]) => vec![vec![!x, a, b], vec![x, !a], vec![x, !b]],
+        (Expression::Xor(_, _), &[a, b]) => vec![vec![!x, a, b], vec![!x, !a, !b], vec![x, !a, b], vec![x, a, !b]],
+        (Expression::Implication(_, _), &[a, b]) => vec![vec![!x, !a, b], vec![x, a], vec![x, !b]],
+        (Expression::Equivalence(_, _), &[a, b]) => vec![vec![!x, !a, b], vec![!x, a, !b], vec![x, a, b], vec![x, !a, !b]],
+        _ => unreachable!(), // Variables and negations have no definition, operators two operands
+    }
+}
+
+// Every node of the expression, each before its children, left before right
+fn pre_order(expr: &Expression) -> Vec<&Expression> {
+    let mut result = Vec::new();
+    let mut stack = vec![expr];
+    while let Some(node) = stack.pop() {
+        result.push(node);
+        match node {
+            Expression::Val(_) | Expression::Var(_) => {},
+            Expression::Neg(child) => stack.push(child),
+            Expression::And(left, right) |
+            Expression::Or(left, right) |
+            Expression::Xor(left, right) |
+            Expression::Implication(left, right) |
+            Expression::Equivalence(left, right) => {
+                stack.push(right);
+                stack.push(left);
+            },
+        }
+    }
+    result
+}
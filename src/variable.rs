@@ -0,0 +1,102 @@
+use std::fmt;
+use std::sync::Arc;
+
+// A propositional variable.
+// The single letters 'A'..='Z' of the exercises are stored inline, anything
+// else is a shared name so clones stay cheap. Names are compared by
+// content and freed with their last variable.
+// Letters sort before names, then each group sorts alphabetically.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Variable(Repr);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Repr {
+    Letter(u8),
+    Named(Arc<str>),
+}
+
+impl Variable {
+    pub fn new(name: &str) -> Self {
+        match name.as_bytes() {
+            [letter @ b'A'..=b'Z'] => Variable(Repr::Letter(*letter)),
+            _ => Variable(Repr::Named(Arc::from(name))),
+        }
+    }
+
+    pub fn letter(&self) -> Option<char> {
+        match &self.0 {
+            Repr::Letter(letter) => Some(*letter as char),
+            Repr::Named(_) => None,
+        }
+    }
+
+    pub fn is_letter(&self) -> bool {
+        matches!(self.0, Repr::Letter(_))
+    }
+}
+
+impl From<char> for Variable {
+    fn from(c: char) -> Self {
+        match c {
+            'A'..='Z' => Variable(Repr::Letter(c as u8)),
+            _ => Variable(Repr::Named(Arc::from(c.encode_utf8(&mut [0; 4]) as &str))),
+        }
+    }
+}
+
+impl From<&str> for Variable {
+    fn from(name: &str) -> Self {
+        Variable::new(name)
+    }
+}
+
+impl From<String> for Variable {
+    fn from(name: String) -> Self {
+        Variable::new(&name)
+    }
+}
+
+impl fmt::Display for Variable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            Repr::Letter(letter) => write!(f, "{}", *letter as char),
+            Repr::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+// Multi-character identifiers start with a lowercase letter or an underscore
+// so they never clash with the single uppercase letters of compact formulas:
+// "AB&" is still A and B, while "door_open alarm_armed &" has two names.
+pub fn is_identifier_start(c: char) -> bool {
+    c.is_ascii_lowercase() || c == '_'
+}
+
+pub fn is_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+// A single letter 'A'..='Z' or an identifier, as written in formulas
+pub fn is_variable_name(name: &str) -> bool {
+    matches!(name.as_bytes(), [b'A'..=b'Z'])
+        || (name.starts_with(is_identifier_start) && name.chars().all(is_identifier_char))
+}
+
+// A..Z when they suffice, otherwise x followed by the numbers from
+// `first_index`, the way the file format being read counts its variables
+pub(crate) fn default_names(count: usize, first_index: usize) -> Vec<Variable> {
+    if count <= 26 {
+        (b'A'..).take(count).map(|letter| Variable::from(letter as char)).collect()
+    } else {
+        (first_index..first_index + count).map(|i| Variable::new(&format!("x{}", i))).collect()
+    }
+}
+
+// Reads the identifier starting at byte `start` and returns it with the byte
+// offset right after its last character.
+pub fn read_identifier(formula: &str, start: usize) -> (Variable, usize) {
+    let end = formula[start..]
+        .find(|c| !is_identifier_char(c))
+        .map_or(formula.len(), |length| start + length);
+    (Variable::new(&formula[start..end]), end)
+}
@@ -1,4 +1,3 @@
-use std::u8;
 use ready_set_boole::adder;
 
 #[test]
@@ -0,0 +1,64 @@
+#![allow(clippy::bool_assert_comparison)]
+
+use ready_set_boole::{eval_formula_with, try_eval_formula_with, Assignment, Expression, LogicError, Variable};
+
+#[test]
+fn test_builder() {
+    let assignment = Assignment::new().with('A', true).with('B', false).with("door_open", true);
+    assert_eq!(assignment.get(&Variable::from('A')), Some(true));
+    assert_eq!(assignment.get(&Variable::from('B')), Some(false));
+    assert_eq!(assignment.get(&Variable::from('C')), None);
+    assert_eq!(assignment.get(&Variable::from("door_open")), Some(true));
+    assert_eq!(assignment.len(), 3);
+}
+
+#[test]
+fn test_overwrite() {
+    let mut assignment = Assignment::new().with('Z', true);
+    assignment.set('Z', false);
+    assert_eq!(assignment.get(&Variable::from('Z')), Some(false));
+    assert_eq!(assignment.len(), 1);
+}
+
+#[test]
+fn test_parse() {
+    let assignment: Assignment = "A=1, B=0,door_open = 1".parse().unwrap();
+    assert_eq!(assignment, Assignment::new().with('A', true).with('B', false).with("door_open", true));
+    assert_eq!(assignment.to_string(), "A=1,B=0,door_open=1");
+    assert_eq!(assignment.to_string().parse::<Assignment>().unwrap(), assignment);
+    assert!("".parse::<Assignment>().unwrap().is_empty());
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!("A=1,B".parse::<Assignment>(), Err(LogicError::InvalidAssignment { position: 5 }));
+    assert_eq!("A=2".parse::<Assignment>(), Err(LogicError::InvalidAssignment { position: 2 }));
+    assert_eq!("A=1, 9=0".parse::<Assignment>(), Err(LogicError::InvalidAssignment { position: 5 }));
+    assert_eq!("AB=1".parse::<Assignment>(), Err(LogicError::InvalidAssignment { position: 0 }));
+}
+
+#[test]
+fn test_eval_formula_with() {
+    let assignment: Assignment = "A=1,B=0".parse().unwrap();
+    assert_eq!(eval_formula_with("AB|", &assignment), true);
+    assert_eq!(eval_formula_with("AB&", &assignment), false);
+    assert_eq!(eval_formula_with("AB>", &assignment), false);
+    assert_eq!(eval_formula_with("A1&B0|=", &assignment), false);
+
+    let assignment: Assignment = "door_open=1,alarm_armed=1".parse().unwrap();
+    assert_eq!(eval_formula_with("door_open alarm_armed ! &", &assignment), false);
+}
+
+#[test]
+fn test_unassigned_variable() {
+    let assignment = Assignment::new().with('A', true);
+    assert_eq!(
+        try_eval_formula_with("AB&", &assignment),
+        Err(LogicError::UnassignedVariable { name: "B".to_string() })
+    );
+    assert_eq!(eval_formula_with("AB&", &assignment), false);
+
+    // B is reported even though A alone decides the result
+    let expr = Expression::from_formula("AB|").unwrap();
+    assert!(expr.evaluate(&assignment).is_err());
+}
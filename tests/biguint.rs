@@ -0,0 +1,36 @@
+use ready_set_boole::BigUint;
+
+#[test]
+fn test_display() {
+    assert_eq!(BigUint::zero().to_string(), "0");
+    assert_eq!(BigUint::from(42u64).to_string(), "42");
+    assert_eq!(BigUint::from(u64::MAX).to_string(), "18446744073709551615");
+    assert_eq!(BigUint::from(u128::MAX).to_string(), "340282366920938463463374607431768211455");
+    assert_eq!(BigUint::power_of_two(100).to_string(), "1267650600228229401496703205376");
+    assert_eq!(BigUint::power_of_two(200).to_string(), "1606938044258990275541962092341162602522202993782792835301376");
+    // A chunk of 19 digits with leading zeros
+    assert_eq!((BigUint::from(10_000_000_000_000_000_000u128) + BigUint::from(7u64)).to_string(), "10000000000000000007");
+}
+
+#[test]
+fn test_arithmetic() {
+    let max = BigUint::from(u64::MAX);
+    assert_eq!((max.clone() + BigUint::one()).to_u128(), Some(1u128 << 64));
+    assert_eq!((&max * &max).to_u128(), Some(u64::MAX as u128 * u64::MAX as u128));
+    assert_eq!(BigUint::from(u128::MAX) + BigUint::one(), BigUint::power_of_two(128));
+    assert_eq!(&BigUint::power_of_two(70) * &BigUint::power_of_two(90), BigUint::power_of_two(160));
+    assert_eq!(&BigUint::zero() * &max, BigUint::zero());
+    assert_eq!(BigUint::from(3u64) << 130, &BigUint::from(3u64) * &BigUint::power_of_two(130));
+    assert_eq!(BigUint::zero() << 10, BigUint::zero());
+    assert_eq!(BigUint::power_of_two(200).to_u128(), None);
+    assert_eq!(BigUint::power_of_two(200).bits(), 201);
+    assert_eq!(BigUint::zero().bits(), 0);
+}
+
+#[test]
+fn test_ordering() {
+    assert!(BigUint::power_of_two(64) > BigUint::from(u64::MAX));
+    assert!(BigUint::from(2u64) < BigUint::from(3u64));
+    assert!(BigUint::zero() < BigUint::one());
+    assert_eq!(BigUint::from(0u128), BigUint::zero());
+}
@@ -0,0 +1,79 @@
+use ready_set_boole::{bitslice::MAX_BITSLICED_VARIABLES, Expression, LogicError, Program, TruthColumn};
+
+fn column(formula: &str) -> TruthColumn {
+    Expression::from_formula(formula).unwrap().evaluate_bitsliced().unwrap()
+}
+
+#[test]
+fn test_small_column() {
+    // Rows in truth table order: A is the most significant bit
+    let column = column("AB&C|");
+    assert_eq!(column.len(), 8);
+    assert!(!column.is_empty());
+    assert_eq!(column.iter().collect::<Vec<_>>(), [false, true, false, true, false, true, true, true]);
+    assert_eq!(column.words(), &[0b1110_1010]);
+    assert_eq!(column.count_ones(), 5);
+}
+
+#[test]
+fn test_constant_columns() {
+    assert_eq!(column("1").words(), &[1]);
+    assert_eq!(column("A!A&").count_ones(), 0);
+    assert_eq!(column("AA!|").count_ones(), 2);
+}
+
+#[test]
+fn test_matches_scalar_program() {
+    let formulas = ["AB&C!|", "ABC&>", "AB^!CD=|", "ABCDEFG&&&&&&", "AB|CD|&EF|&GH|&", "AB^C^D^E^F^G^H^I^"];
+    for formula in formulas {
+        let program = Program::compile(&Expression::from_formula(formula).unwrap()).unwrap();
+        let column = program.truth_column().unwrap();
+        for row in 0..column.len() {
+            assert_eq!(column.get(row), program.evaluate(row), "{formula} row {row}");
+        }
+    }
+}
+
+#[test]
+fn test_lanes() {
+    let program = Program::compile(&Expression::from_formula("AB^").unwrap()).unwrap();
+    assert_eq!(program.evaluate_lanes(&[0b1100, 0b1010]), 0b0110);
+    assert_eq!(program.evaluate_lanes_wide(&[[u64::MAX, 0], [0, 0]]), [u64::MAX, 0]);
+}
+
+#[test]
+fn test_model_counting() {
+    // Parity of 20 variables is true on exactly half of the rows
+    let names: Vec<String> = (0..20).map(|i| format!("x_{i}")).collect();
+    let formula = format!("{} {}", names.join(" "), "^".repeat(19));
+    assert_eq!(column(&formula).count_ones(), 1 << 19);
+
+    // At least one of 16 variables
+    assert_eq!(column("ABCDEFGHIJKLMNOP|||||||||||||||").count_ones(), (1 << 16) - 1);
+}
+
+#[test]
+fn test_is_equivalent() {
+    let a = Expression::from_formula("AB&!").unwrap();
+    let b = Expression::from_formula("A!B!|").unwrap();
+    assert_eq!(a.is_equivalent(&b), Ok(true));
+
+    let c = Expression::from_formula("A!B!&").unwrap();
+    assert_eq!(a.is_equivalent(&c), Ok(false));
+
+    // Variables missing from one side are taken into account
+    let d = Expression::from_formula("AB&!CC!|&").unwrap();
+    assert_eq!(a.is_equivalent(&d), Ok(true));
+    let e = Expression::from_formula("AB&!C&").unwrap();
+    assert_eq!(a.is_equivalent(&e), Ok(false));
+}
+
+#[test]
+fn test_too_many_variables() {
+    let names: Vec<String> = (0..31).map(|i| format!("x_{i}")).collect();
+    let formula = format!("{} {}", names.join(" "), "|".repeat(30));
+    assert_eq!(
+        Expression::from_formula(&formula).unwrap().evaluate_bitsliced(),
+        Err(LogicError::TooManyVariables { count: 31, max: MAX_BITSLICED_VARIABLES })
+    );
+}
@@ -0,0 +1,111 @@
+use ready_set_boole::{
+    cdcl::{cdcl, Cdcl},
+    dpll::dpll,
+    Assignment, Cnf, Expression, Literal,
+};
+
+mod common;
+use common::{pigeonhole, random_cnf, satisfiable, variables, Random};
+
+#[test]
+fn test_matches_brute_force() {
+    let mut random = Random(0x9e3779b97f4a7c15);
+    for _ in 0..500 {
+        let variable_count = 1 + random.next(10) as usize;
+        let clauses = variable_count * 4 + random.next(4) as usize;
+        let width = 1 + random.next(4) as usize;
+        let cnf = random_cnf(&mut random, variable_count, clauses, width);
+        let model = cdcl(&cnf);
+        assert_eq!(model.is_some(), satisfiable(&cnf), "{:?}", cnf.clauses());
+        if let Some(values) = model {
+            assert!(cnf.evaluate(&values));
+        }
+    }
+}
+
+#[test]
+fn test_matches_dpll() {
+    let mut random = Random(0x2545f4914f6cdd1d);
+    for _ in 0..30 {
+        // At the 3-SAT threshold, where instances are hardest
+        let cnf = random_cnf(&mut random, 40, 170, 3);
+        let model = cdcl(&cnf);
+        assert_eq!(model.is_some(), dpll(&cnf).is_some());
+        if let Some(values) = model {
+            assert!(cnf.evaluate(&values));
+        }
+    }
+}
+
+#[test]
+fn test_large_instance() {
+    let mut random = Random(0x853c49e6748fea9b);
+    // Below the threshold, satisfiable with high probability
+    let cnf = random_cnf(&mut random, 300, 1000, 3);
+    let mut solver = Cdcl::new(&cnf);
+    let values = solver.solve().unwrap();
+    assert!(cnf.evaluate(&values));
+    assert!(solver.statistics().decisions > 0);
+    assert!(solver.statistics().propagations >= 300);
+}
+
+#[test]
+fn test_pigeonhole() {
+    let mut solver = Cdcl::new(&pigeonhole(8, 7));
+    assert_eq!(solver.solve(), None);
+    let statistics = *solver.statistics();
+    assert!(statistics.conflicts > 0);
+    assert_eq!(statistics.learned_clauses, statistics.conflicts - 1);
+    assert!(statistics.restarts > 0);
+    // Unsatisfiable stays unsatisfiable
+    assert_eq!(solver.solve(), None);
+
+    assert!(cdcl(&pigeonhole(7, 7)).is_some());
+}
+
+#[test]
+fn test_special_cases() {
+    assert_eq!(cdcl(&Cnf::default()), Some(vec![]));
+    let mut empty_clause = Cnf::new(variables(1));
+    empty_clause.add_clause(vec![]);
+    assert_eq!(cdcl(&empty_clause), None);
+
+    let mut units = Cnf::new(variables(2));
+    units.add_clause(vec![Literal::positive(0)]);
+    units.add_clause(vec![Literal::negative(1), Literal::negative(1)]);
+    units.add_clause(vec![Literal::positive(1), Literal::negative(1)]);
+    assert_eq!(cdcl(&units), Some(vec![true, false]));
+    units.add_clause(vec![Literal::negative(0), Literal::positive(1)]);
+    assert_eq!(cdcl(&units), None);
+}
+
+#[test]
+fn test_expression() {
+    for (formula, satisfiable) in [("AB&C|", true), ("AB^AB=&", false), ("AB>BC>&AC!&&", false), ("AB>BC>&AC&&", true)] {
+        let expr = Expression::from_formula(formula).unwrap();
+        let model = Cdcl::from_expression(&expr).solve();
+        assert_eq!(model.is_some(), satisfiable, "{}", formula);
+        if let Some(values) = model {
+            // The variables of the expression come first
+            let mut assignment = Assignment::new();
+            for (var, &value) in expr.sorted_variables().into_iter().zip(&values) {
+                assignment.set(var, value);
+            }
+            assert!(expr.evaluate(&assignment).unwrap(), "{}", formula);
+        }
+    }
+}
+
+#[test]
+fn test_incremental() {
+    // Blocking each model in turn enumerates the 3 models of A | B
+    let cnf = Cnf::from_expression(&Expression::from_formula("AB|").unwrap()).unwrap();
+    let mut solver = Cdcl::new(&cnf);
+    let mut models = Vec::new();
+    while let Some(values) = solver.solve() {
+        solver.add_clause(values.iter().enumerate().map(|(i, &value)| Literal::new(i, value)).collect());
+        models.push(values);
+    }
+    models.sort();
+    assert_eq!(models, [vec![false, true], vec![true, false], vec![true, true]]);
+}
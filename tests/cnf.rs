@@ -0,0 +1,104 @@
+use ready_set_boole::{sat::sat, simplified_conjunctive_normal_form, Cnf, CnfEncoding, Expression, Literal, LogicError};
+
+mod common;
+use common::satisfiable;
+
+fn expr(formula: &str) -> Expression {
+    Expression::from_formula(formula).unwrap()
+}
+
+fn cnf(formula: &str) -> Cnf {
+    Cnf::from_expression(&expr(formula)).unwrap()
+}
+
+fn equivalent(a: &Cnf, b: &Cnf) -> bool {
+    let n = a.variables().len();
+    assert_eq!(a.variables(), b.variables());
+    (0..1u64 << n).all(|row| {
+        let values: Vec<bool> = (0..n).map(|i| (row >> i) & 1 == 1).collect();
+        a.evaluate(&values) == b.evaluate(&values)
+    })
+}
+
+#[test]
+fn test_from_expression() {
+    let clauses = cnf("AB!|C&");
+    assert_eq!(clauses.clauses(), [vec![Literal::positive(0), Literal::negative(1)], vec![Literal::positive(2)]]);
+    assert_eq!(clauses.to_rpn(), "AB!|C&");
+
+    // Constants: a true one satisfies its clause, a false one disappears
+    assert_eq!(cnf("A1|B0|&").to_rpn(), "B");
+    assert_eq!(cnf("0").clauses(), [vec![]]);
+    assert_eq!(cnf("1").to_rpn(), "1");
+
+    assert_eq!(Cnf::from_expression(&expr("AB&C|")), Err(LogicError::UnexpectedOperatorCNF));
+    assert_eq!(Cnf::from_expression(&expr("AB^")), Err(LogicError::UnexpectedOperatorCNF));
+    assert_eq!(Cnf::from_expression(&expr("A!!")), Err(LogicError::UnexpectedOperatorCNF));
+}
+
+#[test]
+fn test_to_cnf_with() {
+    for formula in ["AB&C|", "AB^C=", "AB>C!|D^", "AB=!"] {
+        let expected = expr(formula);
+        let clauses = expected.to_cnf_with(CnfEncoding::Distributive).unwrap();
+        assert!(clauses.to_expression().is_equivalent(&expected).unwrap(), "{}", formula);
+        for encoding in [CnfEncoding::Tseitin, CnfEncoding::PlaistedGreenbaum] {
+            assert_eq!(satisfiable(&expected.to_cnf_with(encoding).unwrap()), sat(formula), "{}", formula);
+        }
+    }
+}
+
+#[test]
+fn test_remove_duplicates_and_tautologies() {
+    let mut clauses = cnf("AAB||BA|&AA!|&");
+    clauses.remove_duplicates();
+    assert_eq!(clauses.to_rpn(), "AB|AA!|&");
+    clauses.remove_tautologies();
+    assert_eq!(clauses.to_rpn(), "AB|");
+}
+
+#[test]
+fn test_propagate_units() {
+    let mut clauses = cnf("AA!B|&BC|&C!D|&");
+    clauses.propagate_units();
+    // A, then B, which satisfies B | C
+    assert_eq!(clauses.to_rpn(), "ABC!D|&&");
+
+    let mut clauses = cnf("AA!B|&B!&");
+    clauses.propagate_units();
+    assert!(clauses.has_empty_clause());
+    assert_eq!(clauses.clauses().len(), 1);
+}
+
+#[test]
+fn test_eliminate_pure_literals() {
+    // A only appears positively, then B only negatively once A's clauses are gone
+    let mut clauses = cnf("AB|AC!|&B!C|&B!C!|&");
+    clauses.eliminate_pure_literals();
+    assert!(clauses.clauses().is_empty());
+
+    let mut clauses = cnf("AB|A!B!|&");
+    clauses.eliminate_pure_literals();
+    assert_eq!(clauses.clauses().len(), 2);
+}
+
+#[test]
+fn test_remove_subsumed() {
+    let mut clauses = cnf("ABC||AB|&A!C|&CA!|B|&");
+    clauses.remove_subsumed();
+    assert_eq!(clauses.to_rpn(), "AB|A!C|&");
+}
+
+#[test]
+fn test_simplify_keeps_equivalence() {
+    for formula in ["AB&C|", "AB^C=", "AB>C!|D^", "AB=!", "AA!&B|", "AB|A!B|&AB!|&"] {
+        let original = expr(formula).to_cnf_with(CnfEncoding::Distributive).unwrap();
+        let mut simplified = original.clone();
+        simplified.simplify();
+        assert!(equivalent(&original, &simplified), "{}", formula);
+        assert!(simplified.clauses().len() <= original.clauses().len(), "{}", formula);
+    }
+    assert_eq!(simplified_conjunctive_normal_form("AA!B|&", CnfEncoding::Distributive), "AB&");
+    assert_eq!(simplified_conjunctive_normal_form("AA!&", CnfEncoding::Distributive), "0");
+    assert_eq!(simplified_conjunctive_normal_form("A&", CnfEncoding::Distributive), "");
+}
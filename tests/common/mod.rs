@@ -0,0 +1,66 @@
+// Helpers shared by the solver and counter tests, each test crate using a part of them
+#![allow(dead_code)]
+
+use ready_set_boole::{sat::sat_expression_brute_force, Cnf, Literal, Variable};
+
+// xorshift64, enough to draw reproducible instances
+pub struct Random(pub u64);
+
+impl Random {
+    pub fn next(&mut self, bound: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % bound
+    }
+}
+
+// x0, x1, ...
+pub fn variables(count: usize) -> Vec<Variable> {
+    (0..count).map(|i| Variable::new(&format!("x{}", i))).collect()
+}
+
+// Random RPN formula over the first `variables` letters
+pub fn random_formula(random: &mut Random, variables: u64, size: usize) -> String {
+    if size == 0 {
+        return ((b'A' + random.next(variables) as u8) as char).to_string();
+    }
+    if random.next(5) == 0 {
+        return random_formula(random, variables, size - 1) + "!";
+    }
+    let left = random.next(size as u64) as usize;
+    let operator = ['&', '|', '^', '>', '='][random.next(5) as usize];
+    random_formula(random, variables, left) + &random_formula(random, variables, size - 1 - left) + &operator.to_string()
+}
+
+// `clauses` clauses of `width` literals drawn over x0 .. x(variable_count - 1)
+pub fn random_cnf(random: &mut Random, variable_count: usize, clauses: usize, width: usize) -> Cnf {
+    let mut cnf = Cnf::new(variables(variable_count));
+    for _ in 0..clauses {
+        let clause = (0..width).map(|_| Literal::new(random.next(variable_count as u64) as usize, random.next(2) == 1)).collect();
+        cnf.add_clause(clause);
+    }
+    cnf
+}
+
+// Oracle trying every row of the truth table of the clauses
+pub fn satisfiable(cnf: &Cnf) -> bool {
+    sat_expression_brute_force(&cnf.to_expression()).unwrap()
+}
+
+// Every pigeon in a hole, no two pigeons in the same hole
+pub fn pigeonhole(pigeons: usize, holes: usize) -> Cnf {
+    let mut cnf = Cnf::new(variables(pigeons * holes));
+    let pigeon = |p: usize, h: usize| p * holes + h;
+    for p in 0..pigeons {
+        cnf.add_clause((0..holes).map(|h| Literal::positive(pigeon(p, h))).collect());
+    }
+    for h in 0..holes {
+        for p in 0..pigeons {
+            for q in p + 1..pigeons {
+                cnf.add_clause(vec![Literal::negative(pigeon(p, h)), Literal::negative(pigeon(q, h))]);
+            }
+        }
+    }
+    cnf
+}
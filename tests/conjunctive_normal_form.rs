@@ -1,4 +1,4 @@
-use ready_set_boole::conjunctive_normal_form;
+use ready_set_boole::{conjunctive_normal_form, try_conjunctive_normal_form, Expression, LogicError};
 
 #[test]
 fn test_negation_of_conjunction() {
@@ -88,3 +88,30 @@ fn test_double_negation() {
     assert_eq!(conjunctive_normal_form(formula), expected);
 }
 
+#[test]
+fn test_expression_to_cnf() {
+    // Xor, implication and equivalence are normalized first
+    for formula in ["AB^", "AB>C^", "AB=C!|", "AB^C^D^"] {
+        let expr = Expression::from_formula(formula).unwrap();
+        let cnf = expr.to_cnf().unwrap();
+        assert!(cnf.is_equivalent(&expr).unwrap(), "{}", formula);
+        assert_eq!(cnf.to_rpn(), conjunctive_normal_form(formula), "{}", formula);
+    }
+    assert_eq!(Expression::from_formula("AB^").unwrap().to_cnf().unwrap().to_rpn(), "AA!|B!A!|AB|B!B|&&&");
+}
+
+#[test]
+fn test_size_limit() {
+    // (a_i & b_i) for 12 pairs distributes to 2^12 clauses
+    let pairs: Vec<String> = (0..12).map(|i| format!("a{i} & b{i}")).collect();
+    let expr = Expression::from_infix(&pairs.join(" | ")).unwrap();
+    assert_eq!(expr.to_cnf_limited(10_000), Err(LogicError::CnfTooLarge { limit: 10_000 }));
+    assert!(expr.to_cnf().is_ok());
+
+    assert_eq!(Expression::from_formula("AB&C|").unwrap().to_cnf_limited(7).unwrap().to_rpn(), "AC|BC|&");
+    assert_eq!(
+        Expression::from_formula("AB&C|").unwrap().to_cnf_limited(6),
+        Err(LogicError::CnfTooLarge { limit: 6 })
+    );
+    assert_eq!(try_conjunctive_normal_form("A|"), Err(LogicError::MissingArgument { position: 1, symbol: '|', expected: 2, found: 1 }));
+}
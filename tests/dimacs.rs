@@ -0,0 +1,88 @@
+use ready_set_boole::{Cnf, CnfEncoding, Expression, Literal, LogicError, Variable};
+
+fn expr(formula: &str) -> Expression {
+    Expression::from_formula(formula).unwrap()
+}
+
+fn line(text: &str) -> usize {
+    match Cnf::from_dimacs(text) {
+        Err(LogicError::Malformed { line, .. }) => line,
+        other => panic!("expected a malformed DIMACS file, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_read() {
+    let text = "\
+c a small instance
+p cnf 3 2
+1 -3 0
+2
+ 3 -1 0
+%
+0
+";
+    let cnf = Cnf::from_dimacs(text).unwrap();
+    assert_eq!(cnf.variables(), [Variable::from('A'), Variable::from('B'), Variable::from('C')]);
+    assert_eq!(
+        cnf.clauses(),
+        [vec![Literal::positive(0), Literal::negative(2)], vec![Literal::positive(1), Literal::positive(2), Literal::negative(0)]]
+    );
+    assert_eq!(cnf.to_rpn(), "AC!|BCA!||&");
+
+    // Last clause without its 0
+    assert_eq!(Cnf::from_dimacs("p cnf 1 1\n-1").unwrap().to_rpn(), "A!");
+    assert_eq!(Cnf::from_dimacs("p cnf 2 0\n").unwrap().to_rpn(), "1");
+}
+
+#[test]
+fn test_write() {
+    let cnf = expr("AB!|C&").to_cnf_with(CnfEncoding::Distributive).unwrap();
+    assert_eq!(cnf.to_dimacs(), "c var 1 A\nc var 2 B\nc var 3 C\np cnf 3 2\n1 -2 0\n3 0\n");
+    assert_eq!(expr("AB!|C&").to_dimacs(CnfEncoding::Distributive).unwrap(), cnf.to_dimacs());
+}
+
+#[test]
+fn test_round_trip() {
+    for formula in ["AB&C|", "AB^C=", "AB>C!|D^", "AB=!", "XZ|Y!&"] {
+        let original = expr(formula);
+        for encoding in [CnfEncoding::Distributive, CnfEncoding::Tseitin] {
+            let cnf = original.to_cnf_with(encoding).unwrap();
+            assert_eq!(Cnf::from_dimacs(&cnf.to_dimacs()).unwrap(), cnf, "{}", formula);
+        }
+        let read = Expression::from_dimacs(&original.to_dimacs(CnfEncoding::Distributive).unwrap()).unwrap();
+        assert!(read.is_equivalent(&original).unwrap(), "{}", formula);
+    }
+}
+
+#[test]
+fn test_names() {
+    // Named variables keep their names, the others get default ones
+    let cnf = Cnf::from_dimacs("c var 2 Z\np cnf 3 1\n1 2 3 0\n").unwrap();
+    assert_eq!(cnf.variables(), [Variable::from('A'), Variable::from('Z'), Variable::from('C')]);
+
+    // A default name already taken falls back to the number
+    let cnf = Cnf::from_dimacs("c var 1 B\np cnf 2 1\n1 2 0\n").unwrap();
+    assert_eq!(cnf.variables(), [Variable::from('B'), Variable::new("x2")]);
+
+    let text: String = "p cnf 30 1\n".to_string() + "30 0\n";
+    let cnf = Cnf::from_dimacs(&text).unwrap();
+    assert_eq!(cnf.variables()[29], Variable::new("x30"));
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(line(""), 1);
+    assert_eq!(line("c only a comment\n"), 1);
+    assert_eq!(line("1 2 0\np cnf 2 1\n"), 1);
+    assert_eq!(line("p cnf 2\n"), 1);
+    assert_eq!(line("p dnf 2 1\n"), 1);
+    assert_eq!(line("p cnf two 1\n"), 1);
+    assert_eq!(line("p cnf 2 1\np cnf 2 1\n"), 2);
+    assert_eq!(line("p cnf 2 1\n1 3 0\n"), 2);
+    assert_eq!(line("p cnf 2 1\n1 x 0\n"), 2);
+    assert_eq!(line("c\np cnf 2 2\n1 2 0\n"), 2);
+    assert_eq!(line("c var 3 A\np cnf 2 0\n"), 1);
+    assert_eq!(line("c var 1 a-b\np cnf 2 0\n"), 1);
+    assert_eq!(line("c var 1 A\nc var 2 A\np cnf 2 0\n"), 2);
+}
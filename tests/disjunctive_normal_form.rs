@@ -0,0 +1,118 @@
+use ready_set_boole::{disjunctive_normal_form, try_disjunctive_normal_form, Expression, LogicError};
+
+#[test]
+fn test_negation_of_conjunction() {
+    // Test for !(A & B) = !A | !B (De Morgan's law)
+    let formula = "AB&!";
+    let expected = "A!B!|";
+    assert_eq!(disjunctive_normal_form(formula), expected);
+}
+
+#[test]
+fn test_negation_of_disjunction() {
+    // Test for !(A | B) = !A & !B (De Morgan's law)
+    let formula = "AB|!";
+    let expected = "A!B!&";
+    assert_eq!(disjunctive_normal_form(formula), expected);
+}
+
+#[test]
+fn test_conjunction_with_disjunction() {
+    // Test for (A | B) & C = (A & C) | (B & C) (distributive law)
+    let formula = "AB|C&";
+    let expected = "AC&BC&|";
+    assert_eq!(disjunctive_normal_form(formula), expected);
+}
+
+#[test]
+fn test_multiple_disjunctions() {
+    // Test for A | B | C | D = A | B | C | D (already in DNF)
+    let formula = "AB|C|D|";
+    let expected = "ABCD|||";
+    assert_eq!(disjunctive_normal_form(formula), expected);
+}
+
+#[test]
+fn test_multiple_conjunctions() {
+    // Test for A & B & C & D = A & B & C & D (already in DNF)
+    let formula = "AB&C&D&";
+    let expected = "ABCD&&&";
+    assert_eq!(disjunctive_normal_form(formula), expected);
+}
+
+#[test]
+fn test_negated_conjunction_with_negated_var() {
+    // Test for !(A & B) | !C = !A | !B | !C
+    let formula = "AB&!C!|";
+    let expected = "A!B!C!||";
+    assert_eq!(disjunctive_normal_form(formula), expected);
+}
+
+#[test]
+fn test_negated_disjunction_with_negated_var() {
+    // Test for !(A | B) & !C = !A & !B & !C
+    let formula = "AB|!C!&";
+    let expected = "A!B!C!&&";
+    assert_eq!(disjunctive_normal_form(formula), expected);
+}
+
+#[test]
+fn test_implication() {
+    // Test for A => B = !A | B
+    let formula = "AB>";
+    let expected = "A!B|";
+    assert_eq!(disjunctive_normal_form(formula), expected);
+}
+
+#[test]
+fn test_equivalence() {
+    // Test for A <=> B = (!A | B) & (!B | A) = (!A & !B) | (B & !B) | (!A & A) | (B & A)
+    let formula = "AB=";
+    let expected = "A!B!&BB!&A!A&BA&|||";
+    assert_eq!(disjunctive_normal_form(formula), expected);
+}
+
+#[test]
+fn test_complex_distribution() {
+    // Test for A & (B | C) = (A & B) | (A & C) (distributive law)
+    let formula = "ABC|&";
+    let expected = "AB&AC&|";
+    assert_eq!(disjunctive_normal_form(formula), expected);
+}
+
+#[test]
+fn test_double_negation() {
+    // Test for !!A = A
+    let formula = "A!!";
+    let expected = "A";
+    assert_eq!(disjunctive_normal_form(formula), expected);
+}
+
+#[test]
+fn test_equivalent_to_input() {
+    for formula in ["AB^C|", "AB|CD|&", "AB>C=!", "AB&C|D^"] {
+        let dnf = Expression::from_formula(&disjunctive_normal_form(formula)).unwrap();
+        assert!(dnf.is_equivalent(&Expression::from_formula(formula).unwrap()).unwrap(), "{}", formula);
+    }
+}
+
+#[test]
+fn test_invalid_formula() {
+    assert_eq!(disjunctive_normal_form("AB"), "");
+}
+
+#[test]
+fn test_size_limit() {
+    // (a_i | b_i) for 12 pairs distributes to 2^12 terms
+    let pairs: Vec<String> = (0..12).map(|i| format!("(a{i} | b{i})")).collect();
+    let expr = Expression::from_infix(&pairs.join(" & ")).unwrap();
+    assert_eq!(expr.to_dnf_limited(10_000), Err(LogicError::DnfTooLarge { limit: 10_000 }));
+    assert!(expr.to_dnf().is_ok());
+
+    assert_eq!(Expression::from_formula("AB|C&").unwrap().to_dnf_limited(7).unwrap().to_rpn(), "AC&BC&|");
+    assert_eq!(
+        Expression::from_formula("AB|C&").unwrap().to_dnf_limited(6),
+        Err(LogicError::DnfTooLarge { limit: 6 })
+    );
+    assert_eq!(try_disjunctive_normal_form("A&"), Err(LogicError::MissingArgument { position: 1, symbol: '&', expected: 2, found: 1 }));
+}
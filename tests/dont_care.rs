@@ -0,0 +1,94 @@
+use ready_set_boole::{
+    truth_table::TruthTable, Assignment, Expression, IncompleteFunction, KarnaughMap,
+};
+
+fn expr(formula: &str) -> Expression {
+    Expression::from_formula(formula).unwrap()
+}
+
+// BCD digits 10 to 15 never occur: A & B | A & C over (A, B, C, D)
+fn bcd(formula: &str) -> IncompleteFunction {
+    expr(formula).with_dont_care(expr("AB&AC&|"))
+}
+
+#[test]
+fn test_from_assignments() {
+    let assignments: Vec<Assignment> = ["A=1,B=0,C=1,D=0", "A=1,B=1,C=1,D=1"].iter().map(|s| s.parse().unwrap()).collect();
+    let function = IncompleteFunction::from_assignments(expr("AB&CD&|"), &assignments);
+    let table = function.truth_table().unwrap();
+    assert_eq!(table.dont_cares(), [0b1010, 0b1111]);
+    assert_eq!(table.minterms(), [0b0011, 0b0111, 0b1011, 0b1100, 0b1101, 0b1110]);
+
+    // A partial assignment covers every value of the other variables
+    let function = IncompleteFunction::from_assignments(expr("AB|"), &["A=1".parse().unwrap()]);
+    assert_eq!(function.truth_table().unwrap().dont_cares(), [0b10, 0b11]);
+}
+
+#[test]
+fn test_truth_table() {
+    let table = expr("AB&").with_dont_care(expr("AB^")).truth_table().unwrap();
+    assert_eq!(table.to_markdown(), "| A | B | = |\n|---|---|---|\n| 0 | 0 | 0 |\n| 0 | 1 | - |\n| 1 | 0 | - |\n| 1 | 1 | 1 |\n");
+    assert_eq!(table.minterms(), [3]);
+    assert_eq!(table.maxterms(), [0]);
+    assert!(table.row(1).dont_care);
+
+    // Round trip through markdown keeps the don't cares
+    assert_eq!(TruthTable::from_markdown(&table.to_markdown()).unwrap(), table);
+}
+
+#[test]
+fn test_table_equality_ignores_dont_care_outputs() {
+    // A & B and A | B only differ where A ^ B, the don't cares
+    let and = expr("AB&").with_dont_care(expr("AB^")).truth_table().unwrap();
+    let or = expr("AB|").with_dont_care(expr("AB^")).truth_table().unwrap();
+    assert_eq!(and, or);
+
+    // Still different outside of them, or with other don't cares
+    let xor = expr("AB^").with_dont_care(expr("AB^")).truth_table().unwrap();
+    assert_ne!(and, xor);
+    assert_ne!(and, expr("AB&").with_dont_care(expr("AB&")).truth_table().unwrap());
+    assert_ne!(and, TruthTable::from_expression(&expr("AB&")).unwrap());
+}
+
+#[test]
+fn test_variables_of_the_dont_care_set() {
+    let function = expr("AB|").with_dont_care(expr("C"));
+    assert_eq!(function.variables().len(), 3);
+    assert_eq!(function.truth_table().unwrap().dont_cares(), [1, 3, 5, 7]);
+}
+
+#[test]
+fn test_minimization() {
+    // Digit is 9 (1001) without don't cares: A & !B & !C & D, with them: A & D
+    let nine = bcd("AB!C!D&&&");
+    assert_eq!(nine.to_minimal_dnf().unwrap().to_rpn(), "AD&");
+    assert_eq!(nine.to_heuristic_dnf().unwrap().to_rpn(), "AD&");
+
+    // Digit is at least 5
+    let at_least_five = bcd("ABCD|&|");
+    assert_eq!(at_least_five.to_minimal_dnf().unwrap().to_rpn(), "ABC&BD&||");
+    let heuristic = at_least_five.to_heuristic_dnf().unwrap();
+    assert!(at_least_five.is_equivalent(&heuristic).unwrap());
+}
+
+#[test]
+fn test_equivalence_on_the_care_set() {
+    let nine = bcd("AB!C!D&&&");
+    assert!(nine.is_equivalent(&expr("AD&")).unwrap());
+    assert!(!nine.is_equivalent(&expr("A")).unwrap());
+    assert!(!expr("AD&").is_equivalent(&expr("AB!C!D&&&")).unwrap());
+
+    // Variables only in the other expression
+    assert!(expr("A").with_dont_care(expr("A!")).is_equivalent(&expr("AE|")).unwrap());
+    assert!(!expr("A").with_dont_care(expr("A!")).is_equivalent(&expr("AE&")).unwrap());
+}
+
+#[test]
+fn test_karnaugh_map() {
+    let table = bcd("AB!C!D&&&").truth_table().unwrap();
+    let map = KarnaughMap::from_table(&table).unwrap().with_minimal_cover();
+    assert!(map.is_dont_care(2, 3)); // AB = 11, CD = 10
+    let text = map.to_text();
+    assert!(text.contains("   11 | -  | -a | -a | -\n"), "{}", text);
+    assert!(text.ends_with("a: A & D\n"), "{}", text);
+}
@@ -0,0 +1,74 @@
+use ready_set_boole::{
+    dpll::dpll,
+    sat::{sat, sat_brute_force},
+    Cnf, Expression, Literal, Variable,
+};
+
+mod common;
+use common::{pigeonhole, random_cnf, random_formula, satisfiable, variables, Random};
+
+#[test]
+fn test_matches_brute_force_on_formulas() {
+    let mut random = Random(0x2545f4914f6cdd1d);
+    for _ in 0..500 {
+        let size = random.next(12) as usize;
+        let formula = random_formula(&mut random, 5, size);
+        assert_eq!(sat(&formula), sat_brute_force(&formula), "{}", formula);
+    }
+}
+
+#[test]
+fn test_matches_brute_force_on_clauses() {
+    let mut random = Random(0x9e3779b97f4a7c15);
+    for _ in 0..300 {
+        // Around the 3-SAT threshold of 4.26 clauses per variable
+        let variables = 3 + random.next(8) as usize;
+        let clauses = variables * 4 + random.next(3) as usize;
+        let cnf = random_cnf(&mut random, variables, clauses, 3);
+        let model = dpll(&cnf);
+        assert_eq!(model.is_some(), satisfiable(&cnf), "{:?}", cnf.clauses());
+        if let Some(values) = model {
+            assert!(cnf.evaluate(&values));
+        }
+    }
+}
+
+#[test]
+fn test_special_cases() {
+    assert_eq!(dpll(&Cnf::default()), Some(vec![]));
+    let mut empty_clause = Cnf::new(vec![Variable::from('A')]);
+    empty_clause.add_clause(vec![]);
+    assert_eq!(dpll(&empty_clause), None);
+
+    let cnf = Cnf::from_expression(&Expression::from_formula("AB|A!&").unwrap()).unwrap();
+    assert_eq!(dpll(&cnf), Some(vec![false, true]));
+}
+
+#[test]
+fn test_many_variables() {
+    // A chain of 26 implications from A to Z, with A and !Z: out of reach of brute force
+    let letters: Vec<char> = ('A'..='Z').collect();
+    let implications: Vec<String> = letters.windows(2).map(|pair| format!("({} > {})", pair[0], pair[1])).collect();
+    let chain = implications.join(" & ");
+    assert!(!sat(&Expression::from_infix(&format!("{} & A & !Z", chain)).unwrap().to_rpn()));
+    assert!(sat(&Expression::from_infix(&format!("{} & A & Z", chain)).unwrap().to_rpn()));
+
+    // Pigeonhole: 6 pigeons in 5 holes
+    let cnf = pigeonhole(6, 5);
+    assert_eq!(dpll(&cnf), None);
+}
+
+#[test]
+fn test_deep_search() {
+    // x_i ^ y_i as (x_i | y_i) & (!x_i | !y_i): no units nor pure literals,
+    // so each pair takes a decision and 1000 stay on the decision stack
+    let n = 1000;
+    let mut cnf = Cnf::new(variables(2 * n));
+    for i in 0..n {
+        cnf.add_clause(vec![Literal::positive(2 * i), Literal::positive(2 * i + 1)]);
+        cnf.add_clause(vec![Literal::negative(2 * i), Literal::negative(2 * i + 1)]);
+    }
+
+    let model = dpll(&cnf).unwrap();
+    assert!(cnf.evaluate(&model));
+}
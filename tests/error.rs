@@ -0,0 +1,100 @@
+use ready_set_boole::{
+    sat::try_sat, set::try_eval_set, truth_table::try_print_truth_table, try_conjunctive_normal_form,
+    try_eval_formula, try_negation_normal_form, Expression, LogicError,
+};
+
+#[test]
+fn test_missing_argument() {
+    assert_eq!(
+        try_eval_formula("1|0"),
+        Err(LogicError::MissingArgument { position: 1, symbol: '|', expected: 2, found: 1 })
+    );
+    assert_eq!(
+        try_eval_formula("!"),
+        Err(LogicError::MissingArgument { position: 0, symbol: '!', expected: 1, found: 0 })
+    );
+}
+
+#[test]
+fn test_unrecognized_symbol() {
+    assert_eq!(
+        try_eval_formula("10x"),
+        Err(LogicError::UnrecognizedSymbol { position: 2, symbol: 'x' })
+    );
+    assert_eq!(
+        Expression::from_formula("AB&?"),
+        Err(LogicError::UnrecognizedSymbol { position: 3, symbol: '?' })
+    );
+}
+
+#[test]
+fn test_incomplete_formula() {
+    assert_eq!(try_eval_formula("10"), Err(LogicError::IncompleteFormula { position: 2, depth: 2 }));
+    assert_eq!(try_sat(""), Err(LogicError::IncompleteFormula { position: 0, depth: 0 }));
+}
+
+#[test]
+fn test_unknown_set_variable() {
+    assert_eq!(
+        try_eval_set("AB&", vec![vec![1, 2]]),
+        Err(LogicError::UnknownVar { position: 1, symbol: 'B' })
+    );
+}
+
+#[test]
+fn test_infix_positions() {
+    assert_eq!(
+        Expression::from_infix("A & "),
+        Err(LogicError::MissingArgument { position: 2, symbol: '&', expected: 2, found: 1 })
+    );
+    assert_eq!(
+        Expression::from_infix("(A | B"),
+        Err(LogicError::UnbalancedParenthesis { position: 0 })
+    );
+    assert_eq!(
+        Expression::from_infix("A | B)"),
+        Err(LogicError::UnbalancedParenthesis { position: 5 })
+    );
+    assert_eq!(
+        Expression::from_infix("A ⇒ ?"),
+        Err(LogicError::UnrecognizedSymbol { position: 6, symbol: '?' })
+    );
+}
+
+#[test]
+fn test_unsatisfiable_is_not_an_error() {
+    assert_eq!(try_sat("AA!&"), Ok(false));
+    assert!(try_sat("AA!").is_err());
+    assert!(try_negation_normal_form("AB").is_err());
+    assert!(try_conjunctive_normal_form("A&").is_err());
+    assert!(try_print_truth_table("A#").is_err());
+}
+
+#[test]
+fn test_render() {
+    let err = try_eval_formula("1|0").unwrap_err();
+    assert_eq!(err.render("1|0"), "error: '|' expects 2 operands, found 1\n  1|0\n   ^");
+
+    // Carets are aligned on characters, not bytes
+    let err = Expression::from_infix("A ⇒ ?").unwrap_err();
+    assert_eq!(err.render("A ⇒ ?"), "error: unrecognized symbol '?'\n  A ⇒ ?\n      ^");
+}
+
+#[test]
+fn test_display() {
+    let err = try_eval_formula("10x").unwrap_err();
+    assert_eq!(err.to_string(), "unrecognized symbol 'x' (at offset 2)");
+    let boxed: Box<dyn std::error::Error> = Box::new(err);
+    assert!(!boxed.to_string().is_empty());
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_pop_from_stack() {
+    let mut stack = vec![1];
+    assert_eq!(ready_set_boole::pop_from_stack(&mut stack), Ok(1));
+    assert_eq!(
+        ready_set_boole::pop_from_stack::<i32>(&mut stack),
+        Err(LogicError::MissingArgument { position: 0, symbol: '?', expected: 1, found: 0 })
+    );
+}
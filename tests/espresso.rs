@@ -0,0 +1,159 @@
+use ready_set_boole::{
+    espresso::{complement, cube_covers, espresso, is_tautology},
+    heuristic_sum_of_products, minimal_sum_of_products, Cube, Expression, LogicError, Pla,
+};
+
+fn covered(cover: &[Cube], row: u64) -> bool {
+    cover.iter().any(|cube| cube.covers(row))
+}
+
+#[test]
+fn test_tautology() {
+    // A | !A
+    assert!(is_tautology(1, &[Cube::new(1, 1), Cube::new(1, 0)]));
+    // A | !A & B
+    assert!(!is_tautology(2, &[Cube::new(0b10, 0b10), Cube::new(0b11, 0b01)]));
+    // A & B | !A | !B
+    assert!(is_tautology(2, &[Cube::new(0b11, 0b11), Cube::new(0b10, 0), Cube::new(0b01, 0)]));
+    assert!(is_tautology(3, &[Cube::UNIVERSE]));
+    assert!(!is_tautology(3, &[]));
+}
+
+#[test]
+fn test_complement() {
+    let cover = [Cube::new(0b1100, 0b1000), Cube::new(0b0011, 0b0011), Cube::new(0b1010, 0b0010)];
+    let missing = complement(4, &cover);
+    for row in 0..16 {
+        assert_ne!(covered(&cover, row), covered(&missing, row), "row {}", row);
+    }
+    assert_eq!(complement(2, &[]), [Cube::UNIVERSE]);
+    assert_eq!(complement(2, &[Cube::UNIVERSE]), []);
+}
+
+#[test]
+fn test_cube_covers() {
+    for formula in ["AB&C|", "AB^C=", "AB>C!|D^", "AB=!"] {
+        let expr = Expression::from_formula(formula).unwrap();
+        let variables = expr.sorted_variables();
+        let (on, off) = cube_covers(&expr, &variables).unwrap();
+        let table = expr.evaluate_bitsliced().unwrap();
+        for row in 0..table.len() {
+            assert_eq!(covered(&on, row), table.get(row), "{} row {}", formula, row);
+            assert_eq!(covered(&off, row), !table.get(row), "{} row {}", formula, row);
+        }
+    }
+}
+
+#[test]
+fn test_heuristic_sum_of_products() {
+    assert_eq!(heuristic_sum_of_products("AB&AB!&|"), "A");
+    assert_eq!(heuristic_sum_of_products("AB>"), "A!B|");
+    assert_eq!(heuristic_sum_of_products("AA!|"), "1");
+    assert_eq!(heuristic_sum_of_products("AA!&"), "0");
+    assert_eq!(heuristic_sum_of_products("A&"), "");
+}
+
+#[test]
+fn test_matches_exact_minimization() {
+    for formula in ["AB&C|", "AB>C=", "AB|C!&DA^|", "AB&A!C&|BC&|", "ABC&&A!B!C!&&|D|"] {
+        let expr = Expression::from_formula(formula).unwrap();
+        let heuristic = expr.to_heuristic_dnf().unwrap();
+        assert!(heuristic.is_equivalent(&expr).unwrap(), "{}", formula);
+        // Both are sums of products, compare their number of products
+        let products = |rpn: &str| rpn.matches('|').count();
+        assert_eq!(products(&heuristic.to_rpn()), products(&minimal_sum_of_products(formula)), "{}", formula);
+    }
+}
+
+#[test]
+fn test_many_variables() {
+    // (a_i & b_i) | (a_i & !b_i) for 12 pairs, 24 variables: simplifies to a_0 | ... | a_11
+    let pairs: Vec<String> = (0..12).map(|i| format!("(a{i} & b{i} | a{i} & !b{i})")).collect();
+    let expr = Expression::from_infix(&pairs.join(" | ")).unwrap();
+    let minimized = expr.to_heuristic_dnf().unwrap();
+
+    let expected: Vec<String> = (0..12).map(|i| format!("a{}", i)).collect();
+    let expected = Expression::from_infix(&expected.join(" | ")).unwrap();
+    assert!(minimized.is_equivalent(&expected).unwrap());
+    assert_eq!(minimized.variables().len(), 12);
+}
+
+#[test]
+fn test_dont_cares() {
+    // On: rows 1 and 3, !A & C. With rows 5 and 7 as don't cares, C is enough
+    let on = [Cube::minterm(1, 3), Cube::minterm(3, 3)];
+    let dont_care = [Cube::new(0b101, 0b101)];
+    let off = complement(3, &[on.to_vec(), dont_care.to_vec()].concat());
+    assert_eq!(espresso(3, &on, &dont_care, &off), [Cube::new(0b001, 0b001)]);
+}
+
+const PLA: &str = "\
+# f = A & B | A & !B & C
+.i 3
+.o 1
+.ilb A B C
+.ob f
+.p 2
+11- 1
+101 1
+.e
+";
+
+#[test]
+fn test_pla() {
+    let pla: Pla = PLA.parse().unwrap();
+    assert_eq!(pla.variables().len(), 3);
+    assert_eq!(pla.on().len(), 2);
+    assert_eq!(pla.to_expression().to_rpn(), "AB&AB!C&&|");
+
+    let minimized = pla.minimize();
+    assert_eq!(minimized.to_expression().to_rpn(), "AB&AC&|");
+    assert_eq!(minimized.to_string(), ".i 3\n.o 1\n.ilb A B C\n.ob f\n.p 2\n11- 1\n1-1 1\n.e\n");
+    let reparsed: Pla = minimized.to_string().parse().unwrap();
+    assert_eq!(reparsed.on(), minimized.on());
+}
+
+#[test]
+fn test_pla_types() {
+    // fr: rows listed nowhere are don't cares, so A alone is enough
+    let pla: Pla = ".i 2\n.o 1\n.type fr\n11 1\n0- 0\n".parse().unwrap();
+    assert_eq!(pla.minimize().to_expression().to_rpn(), "A");
+
+    let pla: Pla = ".i 2\n.o 1\n11 1\n10 -\n".parse().unwrap();
+    assert_eq!(pla.dont_care(), [Cube::new(0b11, 0b10)]);
+    assert_eq!(pla.minimize().to_expression().to_rpn(), "A");
+
+    // Default names
+    let pla: Pla = ".i 2\n.o 1\n1- 1\n".parse().unwrap();
+    assert_eq!(pla.to_expression().to_rpn(), "A");
+}
+
+#[test]
+fn test_pla_from_expression() {
+    let expr = Expression::from_formula("AB&AB!C&&|").unwrap();
+    let pla = Pla::from_expression(&expr).unwrap();
+    assert!(pla.minimize().to_expression().is_equivalent(&expr).unwrap());
+}
+
+#[test]
+fn test_pla_errors() {
+    let line = |text: &str| match text.parse::<Pla>() {
+        Err(LogicError::Malformed { line, .. }) => line,
+        other => panic!("expected a malformed PLA, got {:?}", other),
+    };
+
+    assert_eq!(line(""), 1);
+    assert_eq!(line("11 1\n"), 1);
+    assert_eq!(line(".i 2\n.o 2\n"), 2);
+    assert_eq!(line(".i 2\n.o 1\n1 1\n"), 3);
+    assert_eq!(line(".i 2\n.o 1\n1x 1\n"), 3);
+    assert_eq!(line(".i 2\n.o 1\n11 x\n"), 3);
+    assert_eq!(line(".i 2\n.o 1\n.ilb A\n11 1\n"), 3);
+    assert_eq!(line(".i 2\n.o 1\n.p 3\n11 1\n"), 3);
+    assert_eq!(line(".i 2\n.o 1\n.phase 1\n"), 3);
+    assert_eq!(line(".i 2\n.o 1\n.type fr\n1- 1\n11 0\n"), 5);
+    assert_eq!(
+        ".i 65\n".parse::<Pla>(),
+        Err(LogicError::TooManyVariables { count: 65, max: 64 })
+    );
+}
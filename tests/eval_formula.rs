@@ -1,3 +1,5 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use ready_set_boole::evaluate::eval_formula;
 
 #[test]
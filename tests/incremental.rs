@@ -0,0 +1,62 @@
+use ready_set_boole::{
+    incremental::IncrementalEvaluator,
+    sat::{sat, sat_gray},
+    Expression, Program,
+};
+
+#[test]
+fn test_initial_row() {
+    let evaluator = IncrementalEvaluator::new(&Expression::from_formula("AB|!").unwrap()).unwrap();
+    assert_eq!(evaluator.row(), 0);
+    assert!(evaluator.value());
+}
+
+#[test]
+fn test_flip() {
+    let mut evaluator = IncrementalEvaluator::new(&Expression::from_formula("AB&C|").unwrap()).unwrap();
+    assert!(!evaluator.value());
+    assert!(!evaluator.flip(0)); // A
+    assert!(evaluator.flip(1)); // A B
+    assert_eq!(evaluator.row(), 0b110);
+    assert!(!evaluator.flip(0)); // B
+    assert!(evaluator.flip(2)); // B C
+}
+
+#[test]
+fn test_repeated_variable() {
+    // Both leaves of A change together
+    let mut evaluator = IncrementalEvaluator::new(&Expression::from_formula("AA!&B|").unwrap()).unwrap();
+    assert!(!evaluator.value());
+    assert!(!evaluator.flip(0));
+    assert!(evaluator.flip(1));
+    assert!(!evaluator.flip(1));
+}
+
+#[test]
+fn test_gray_rows_match_program() {
+    let formulas = ["AB&C!|", "ABC&>", "AB^!CD=|", "AB=A^C>", "ABCDEFG&&&&&&", "AB|CD|&EF|&GH|&", "1", "A0|"];
+    for formula in formulas {
+        let expr = Expression::from_formula(formula).unwrap();
+        let program = Program::compile(&expr).unwrap();
+        let evaluator = IncrementalEvaluator::new(&expr).unwrap();
+
+        let mut previous: Option<u64> = None;
+        let mut seen = 0;
+        for (row, value) in evaluator.gray_rows() {
+            assert_eq!(value, program.evaluate(row), "{formula} row {row:b}");
+            if let Some(previous) = previous {
+                assert_eq!((row ^ previous).count_ones(), 1);
+            }
+            previous = Some(row);
+            seen += 1;
+        }
+        assert_eq!(seen, program.row_count());
+    }
+}
+
+#[test]
+fn test_sat_gray() {
+    for formula in ["AB|", "AA!&", "AB&!C!|", "AB&C&D&A!&", "AA=", "AA^"] {
+        assert_eq!(sat_gray(formula), sat(formula), "{formula}");
+    }
+}
@@ -0,0 +1,83 @@
+use ready_set_boole::{normalize, sat::sat, Expression};
+
+fn rpn(infix: &str) -> String {
+    Expression::from_infix(infix).unwrap().to_rpn()
+}
+
+#[test]
+fn test_single_operand() {
+    assert_eq!(rpn("A"), "A");
+    assert_eq!(rpn("1"), "1");
+    assert_eq!(rpn("⊥"), "0");
+    assert_eq!(rpn("  ( ( B ) ) "), "B");
+}
+
+#[test]
+fn test_precedence() {
+    assert_eq!(rpn("A | B & C"), "ABC&|");
+    assert_eq!(rpn("A & B | C"), "AB&C|");
+    assert_eq!(rpn("A ^ B & C | D"), "ABC&^D|");
+    assert_eq!(rpn("A | B > C = D"), "AB|C>D=");
+    assert_eq!(rpn("!A & B"), "A!B&");
+    assert_eq!(rpn("!(A & B)"), "AB&!");
+}
+
+#[test]
+fn test_associativity() {
+    assert_eq!(rpn("A & B & C"), "AB&C&");
+    assert_eq!(rpn("A = B = C"), "AB=C=");
+    assert_eq!(rpn("A > B > C"), "ABC>>");
+}
+
+#[test]
+fn test_parentheses() {
+    assert_eq!(rpn("(A & B) | !C"), "AB&C!|");
+    assert_eq!(rpn("A & (B | C)"), "ABC|&");
+    assert_eq!(rpn("(A > B) > C"), "AB>C>");
+}
+
+#[test]
+fn test_unicode_symbols() {
+    assert_eq!(rpn("(A ∧ B) ∨ ¬C"), "AB&C!|");
+    assert_eq!(rpn("A ⊕ B ⇒ C ⇔ ⊤"), "AB^C>1=");
+}
+
+#[test]
+fn test_same_tree_as_rpn() {
+    let cases = [
+        ("(A & B) | !C", "AB&C!|"),
+        ("A ^ (B = C)", "ABC=^"),
+        ("!(A | B) > C & D", "AB|!CD&>"),
+    ];
+    for (infix, formula) in cases {
+        let from_infix = Expression::from_infix(infix).unwrap();
+        let from_rpn = Expression::from_formula(formula).unwrap();
+        assert_eq!(from_infix, from_rpn);
+        assert_eq!(normalize(&from_infix), normalize(&from_rpn));
+        assert_eq!(sat(&from_infix.to_rpn()), sat(formula));
+    }
+}
+
+#[test]
+fn test_to_infix_round_trip() {
+    for formula in ["AB&C!|", "ABC&|", "AB>C>", "ABC>>", "AB=C=", "ABC==", "AB&!C^", "A!!", "AB^C&"] {
+        let expr = Expression::from_formula(formula).unwrap();
+        let infix = expr.to_infix();
+        assert_eq!(Expression::from_infix(&infix).unwrap(), expr, "{infix}");
+    }
+    assert_eq!(Expression::from_formula("AB&C!|").unwrap().to_infix(), "A & B | !C");
+    assert_eq!(Expression::from_formula("AB|C&").unwrap().to_infix(), "(A | B) & C");
+}
+
+#[test]
+fn test_invalid_formulas() {
+    assert!(Expression::from_infix("").is_err());
+    assert!(Expression::from_infix("A &").is_err());
+    assert!(Expression::from_infix("& A").is_err());
+    assert!(Expression::from_infix("(A | B").is_err());
+    assert!(Expression::from_infix("A | B)").is_err());
+    assert!(Expression::from_infix("A B").is_err());
+    assert!(Expression::from_infix("A + B").is_err());
+    assert!(Expression::from_infix("a b").is_err());
+    assert!(Expression::from_infix("A1").is_err());
+}
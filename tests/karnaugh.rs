@@ -0,0 +1,86 @@
+use ready_set_boole::{karnaugh::MAX_KARNAUGH_VARIABLES, truth_table::TruthTable, Cube, Expression, KarnaughMap, LogicError};
+
+fn map(formula: &str) -> KarnaughMap {
+    KarnaughMap::from_expression(&Expression::from_formula(formula).unwrap()).unwrap()
+}
+
+#[test]
+fn test_layout() {
+    let map = map("AB&C!D&|AC&|");
+    assert_eq!((map.row_count(), map.column_count()), (4, 4));
+    assert_eq!(map.row_variables().len(), 2);
+    // Row AB = 11, column CD = 10
+    assert_eq!(map.table_row(2, 3), 0b1110);
+
+    let map = self::map("AB|C^");
+    assert_eq!((map.row_count(), map.column_count()), (2, 4));
+    assert_eq!(map.table_row(1, 2), 0b111);
+}
+
+#[test]
+fn test_every_cell_matches_the_table() {
+    for formula in ["AB^", "AB|C&", "AB>CD^|", "ABC&&DE|^", "AB^CD^EF^||"] {
+        let map = map(formula);
+        let table = TruthTable::from_formula(formula).unwrap();
+        let mut seen = Vec::new();
+        for row in 0..map.row_count() {
+            for column in 0..map.column_count() {
+                let index = map.table_row(row, column);
+                assert_eq!(map.value(row, column), table.row(index).output, "{}", formula);
+                seen.push(index);
+            }
+        }
+        seen.sort();
+        assert_eq!(seen, (0..table.row_count()).collect::<Vec<_>>(), "{}", formula);
+    }
+}
+
+#[test]
+fn test_adjacent_cells_differ_by_one_variable() {
+    let map = map("ABCDEF&&&&&");
+    for row in 0..map.row_count() {
+        for column in 0..map.column_count() {
+            let right = map.table_row(row, (column + 1) % map.column_count());
+            let below = map.table_row((row + 1) % map.row_count(), column);
+            assert_eq!((map.table_row(row, column) ^ right).count_ones(), 1);
+            assert_eq!((map.table_row(row, column) ^ below).count_ones(), 1);
+        }
+    }
+}
+
+#[test]
+fn test_text() {
+    let text = map("AB&C!D&|AC&|").with_minimal_cover().to_text();
+    let expected = "\
+AB\\CD | 00  | 01  | 11  | 10
+   00 | 0   | 1c  | 0   | 0
+   01 | 0   | 1c  | 0   | 0
+   11 | 1a  | 1ac | 1ab | 1ab
+   10 | 0   | 1c  | 1b  | 1b
+a: A & B
+b: A & C
+c: !C & D
+";
+    assert_eq!(text, expected);
+
+    assert_eq!(map("AB>").to_text(), "A\\B | 0 | 1\n  0 | 1 | 1\n  1 | 0 | 1\n");
+}
+
+#[test]
+fn test_svg() {
+    // !D covers the two outer columns of the map, drawn as one rectangle per side
+    let svg = map("D!ABC&&|").with_groups(vec![Cube::new(0b0001, 0)]).to_svg();
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert_eq!(svg.matches("fill-opacity").count(), 2);
+    assert_eq!(svg.matches("stroke=\"black\"").count(), 16);
+}
+
+#[test]
+fn test_too_many_variables() {
+    let expr = Expression::from_formula("ABCDEFG&&&&&&").unwrap();
+    assert_eq!(
+        KarnaughMap::from_expression(&expr),
+        Err(LogicError::TooManyVariables { count: 7, max: MAX_KARNAUGH_VARIABLES })
+    );
+}
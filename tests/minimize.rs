@@ -0,0 +1,83 @@
+use ready_set_boole::{
+    minimal_sum_of_products, minimize::{minimum_cover, prime_implicants}, truth_table::TruthTable, Cube, Expression,
+    Variable,
+};
+
+#[test]
+fn test_cube() {
+    // A & !C over (A, B, C)
+    let cube = Cube::new(0b101, 0b100);
+    assert!(cube.covers(0b100) && cube.covers(0b110));
+    assert!(!cube.covers(0b101));
+    assert_eq!(cube.rows(3).collect::<Vec<_>>(), [0b100, 0b110]);
+    assert_eq!(cube.literal_count(), 2);
+    assert!(Cube::UNIVERSE.contains(&cube));
+    assert!(!cube.contains(&Cube::UNIVERSE));
+
+    let variables: Vec<Variable> = "ABC".chars().map(Variable::from).collect();
+    assert_eq!(cube.to_product(&variables).to_rpn(), "AC!&");
+    assert_eq!(cube.to_clause(&variables).to_rpn(), "A!C|");
+    assert_eq!(Cube::minterm(0b011, 3).combine(&Cube::minterm(0b111, 3)), Some(Cube::new(0b011, 0b011)));
+    assert_eq!(Cube::minterm(0b011, 3).combine(&Cube::minterm(0b100, 3)), None);
+}
+
+#[test]
+fn test_prime_implicants() {
+    // f(A, B, C, D) = m(4, 8, 10, 11, 12, 15)
+    let primes = prime_implicants(4, &[4, 8, 10, 11, 12, 15]);
+    assert_eq!(
+        primes,
+        [
+            Cube::new(0b1110, 0b1010), // A !B C
+            Cube::new(0b1101, 0b1000), // A !B !D
+            Cube::new(0b1011, 0b1000), // A !C !D
+            Cube::new(0b1011, 0b1011), // A C D
+            Cube::new(0b0111, 0b0100), // B !C !D
+        ]
+    );
+
+    let table = TruthTable::from_formula("AB|").unwrap();
+    assert_eq!(table.prime_implicants(), [Cube::new(0b10, 0b10), Cube::new(0b01, 0b01)]);
+}
+
+#[test]
+fn test_cyclic_cover() {
+    // No essential prime: m(0, 1, 2, 5, 6, 7) has six primes in a cycle,
+    // two minimal covers of three products each
+    let minterms = [0, 1, 2, 5, 6, 7];
+    let primes = prime_implicants(3, &minterms);
+    assert_eq!(primes.len(), 6);
+
+    let cover = minimum_cover(&primes, &minterms);
+    assert_eq!(cover.len(), 3);
+    for row in minterms {
+        assert!(cover.iter().any(|cube| cube.covers(row)));
+    }
+}
+
+#[test]
+fn test_minimal_sum_of_products() {
+    assert_eq!(minimal_sum_of_products("AB&AB!&|"), "A");
+    assert_eq!(minimal_sum_of_products("AB|A&"), "A");
+    assert_eq!(minimal_sum_of_products("AB>"), "A!B|");
+    assert_eq!(minimal_sum_of_products("AB^"), "A!B&AB!&|");
+    assert_eq!(minimal_sum_of_products("AA!|"), "1");
+    assert_eq!(minimal_sum_of_products("AA!&"), "0");
+    assert_eq!(minimal_sum_of_products("AB&C|"), "CAB&|");
+}
+
+#[test]
+fn test_minimal_is_equivalent() {
+    for formula in ["AB&C|D^", "AB>C=", "AB|C!&DA^|", "ABCD&&&A!B!C!D!&&&|", "AB=CD=&"] {
+        let expr = Expression::from_formula(formula).unwrap();
+        let minimal = expr.to_minimal_dnf().unwrap();
+        let canonical = TruthTable::from_expression(&expr).unwrap().to_canonical_dnf();
+        assert!(minimal.is_equivalent(&expr).unwrap(), "{}", formula);
+        assert!(minimal.to_rpn().len() <= canonical.to_rpn().len(), "{}", formula);
+    }
+}
+
+#[test]
+fn test_invalid_formula() {
+    assert_eq!(minimal_sum_of_products("A&"), "");
+}
@@ -0,0 +1,86 @@
+use ready_set_boole::{
+    model_count,
+    model_count::{count_cnf, count_models, count_models_bitsliced, count_models_enumeration},
+    BigUint, Cnf, CnfEncoding, Expression, Literal, LogicError,
+};
+
+mod common;
+use common::{random_formula, variables, Random};
+
+fn expr(formula: &str) -> Expression {
+    Expression::from_formula(formula).unwrap()
+}
+
+#[test]
+fn test_model_count() {
+    assert_eq!(model_count("AB|").to_string(), "3");
+    assert_eq!(model_count("AB&C|").to_string(), "5");
+    assert_eq!(model_count("AA!&").to_string(), "0");
+    assert_eq!(model_count("AA!|").to_string(), "2");
+    assert_eq!(model_count("1").to_string(), "1");
+    assert_eq!(model_count("A|").to_string(), "0");
+}
+
+#[test]
+fn test_counters_agree() {
+    let mut random = Random(0x2545f4914f6cdd1d);
+    for _ in 0..300 {
+        let size = random.next(14) as usize;
+        let formula = random_formula(&mut random, 6, size);
+        let expr = expr(&formula);
+        let expected = count_models_enumeration(&expr).unwrap();
+        assert_eq!(count_models_bitsliced(&expr).unwrap(), expected, "{}", formula);
+        assert_eq!(count_cnf(expr.to_tseitin().cnf()), expected, "{}", formula);
+    }
+}
+
+#[test]
+fn test_components() {
+    // 12 independent pairs (a_i | b_i): 3^12 models over 24 variables
+    let pairs: Vec<String> = (0..12).map(|i| format!("(a{i} | b{i})")).collect();
+    let expr = Expression::from_infix(&pairs.join(" & ")).unwrap();
+    assert_eq!(count_models(&expr), BigUint::from(3u64.pow(12)));
+    assert_eq!(count_cnf(&expr.to_cnf_with(CnfEncoding::Distributive).unwrap()), BigUint::from(3u64.pow(12)));
+}
+
+#[test]
+fn test_large_counts() {
+    // x0 | ... | x199 over 200 variables: all but one assignment
+    let mut cnf = Cnf::new(variables(200));
+    cnf.add_clause((0..200).map(Literal::positive).collect());
+    assert_eq!(count_cnf(&cnf).to_string(), "1606938044258990275541962092341162602522202993782792835301375");
+
+    // A chain of implications x_i > x_i+1 has n + 1 models
+    let chain: Vec<String> = (0..99).map(|i| format!("(x{} > x{})", i, i + 1)).collect();
+    let expr = Expression::from_infix(&chain.join(" & ")).unwrap();
+    assert_eq!(count_models(&expr), BigUint::from(101u64));
+
+    // Variables without clauses are free
+    let mut cnf = Cnf::new(variables(70));
+    cnf.add_clause(vec![Literal::positive(0), Literal::negative(0)]);
+    assert_eq!(count_cnf(&cnf), BigUint::power_of_two(70));
+    cnf.add_clause(vec![]);
+    assert_eq!(count_cnf(&cnf), BigUint::zero());
+}
+
+#[test]
+fn test_limits() {
+    let letters: Vec<String> = ('A'..='Z').map(String::from).collect();
+    let expr = Expression::from_infix(&letters.join(" | ")).unwrap();
+    // Past the bit-sliced path, counted on the clauses
+    assert_eq!(count_models(&expr), BigUint::from((1u64 << 26) - 1));
+
+    let wide: Vec<String> = (0..31).map(|i| format!("x{}", i)).collect();
+    let wide = Expression::from_infix(&wide.join(" | ")).unwrap();
+    assert_eq!(count_models_bitsliced(&wide), Err(LogicError::TooManyVariables { count: 31, max: 30 }));
+}
+
+#[test]
+fn test_long_chain() {
+    // (x0 > x1) & ... & (x999 > x1000): a prefix of false variables then
+    // true ones, n + 2 models over n + 1 variables
+    let n = 1000;
+    let chain: Vec<String> = (0..n).map(|i| format!("(x{} > x{})", i, i + 1)).collect();
+    let expr = Expression::from_infix(&chain.join(" & ")).unwrap();
+    assert_eq!(count_models(&expr), BigUint::from(n as u64 + 2));
+}
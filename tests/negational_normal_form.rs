@@ -1,4 +1,8 @@
-use ready_set_boole::negation_normal_form;
+use ready_set_boole::{
+    negation_normal_form,
+    negation_normal_form::{operand_polarities, Polarity},
+    Expression,
+};
 
 #[test]
 fn test_double_negation() {
@@ -42,3 +46,12 @@ fn test_xor_to_nnf() {
     assert_eq!(nnf_ast, "AB!&A!B&|");
 }
 
+#[test]
+fn test_operand_polarities() {
+    let polarities = |formula: &str, polarity| operand_polarities(&Expression::from_formula(formula).unwrap(), polarity);
+    assert_eq!(polarities("AB&", Polarity::Negative), (Polarity::Negative, Polarity::Negative));
+    assert_eq!(polarities("AB>", Polarity::Positive), (Polarity::Negative, Polarity::Positive));
+    assert_eq!(polarities("A!", Polarity::Positive), (Polarity::Negative, Polarity::Negative));
+    assert_eq!(polarities("AB=", Polarity::Positive), (Polarity::Both, Polarity::Both));
+    assert_eq!(Polarity::Both.flip(), Polarity::Both);
+}
@@ -0,0 +1,93 @@
+use ready_set_boole::{
+    program::{Instruction, MAX_VARIABLES},
+    sat::sat,
+    Assignment, Expression, LogicError, Program, Variable,
+};
+
+// Reference evaluation of `row` through the tree walking evaluator
+fn evaluate_row(expr: &Expression, vars: &[Variable], row: u64) -> bool {
+    let mut assignment = Assignment::new();
+    for (i, var) in vars.iter().enumerate() {
+        assignment.set(var.clone(), (row >> (vars.len() - 1 - i)) & 1 == 1);
+    }
+    expr.evaluate(&assignment).unwrap()
+}
+
+#[test]
+fn test_compile() {
+    let program = Program::compile(&Expression::from_formula("AB&C!|").unwrap()).unwrap();
+    assert_eq!(program.variables(), &[Variable::from('A'), Variable::from('B'), Variable::from('C')]);
+    assert_eq!(
+        program.instructions(),
+        &[Instruction::Load(0), Instruction::Load(1), Instruction::And, Instruction::Load(2), Instruction::Not, Instruction::Or]
+    );
+}
+
+#[test]
+fn test_deeper_operand_first() {
+    // The implication operands are swapped, so its converse is emitted
+    let program = Program::compile(&Expression::from_formula("ABC&>").unwrap()).unwrap();
+    assert_eq!(
+        program.instructions(),
+        &[Instruction::Load(1), Instruction::Load(2), Instruction::And, Instruction::Load(0), Instruction::ConverseImplication]
+    );
+}
+
+#[test]
+fn test_matches_tree_evaluation() {
+    let formulas = ["AB&C!|", "ABC&>", "AB>C>", "ABC>>", "AB=C^", "AB^!CD=|", "A1&B0|=", "ABCDE&&&>!", "AA!&"];
+    for formula in formulas {
+        let expr = Expression::from_formula(formula).unwrap();
+        let program = Program::compile(&expr).unwrap();
+        for row in 0..program.row_count() as u64 {
+            assert_eq!(program.evaluate(row), evaluate_row(&expr, program.variables(), row), "{formula} row {row}");
+        }
+    }
+}
+
+#[test]
+fn test_constant_formula() {
+    let program = Program::compile(&Expression::from_formula("10|").unwrap()).unwrap();
+    assert_eq!(program.row_count(), 1);
+    assert!(program.evaluate(0));
+}
+
+#[test]
+fn test_right_nested_chain_stays_shallow() {
+    // A right-nested chain of 40 implications would need a 40 deep stack in naive order
+    let names: Vec<String> = (0..40).map(|i| format!("x_{i:02}")).collect();
+    let formula = format!("{} {}", names.join(" "), ">".repeat(39));
+    let expr = Expression::from_formula(&formula).unwrap();
+    let program = Program::compile(&expr).unwrap();
+    assert!(program.evaluate(0));
+    assert!(!program.evaluate(u64::MAX >> 24 & !1));
+}
+
+#[test]
+fn test_explicit_variables() {
+    let expr = Expression::from_formula("B").unwrap();
+    let vars = [Variable::from('A'), Variable::from('B')];
+    let program = Program::compile_with_variables(&expr, &vars).unwrap();
+    assert_eq!(program.row_count(), 4);
+    assert!(!program.evaluate(0b10));
+    assert!(program.evaluate(0b01));
+    assert!(Program::compile_with_variables(&expr, &vars[..1]).is_err());
+}
+
+#[test]
+fn test_too_many_variables() {
+    let names: Vec<String> = (0..65).map(|i| format!("x_{i}")).collect();
+    let formula = format!("{} {}", names.join(" "), "|".repeat(64));
+    let expr = Expression::from_formula(&formula).unwrap();
+    assert_eq!(Program::compile(&expr), Err(LogicError::TooManyVariables { count: 65, max: MAX_VARIABLES }));
+}
+
+#[test]
+fn test_twenty_variable_sat() {
+    // Only satisfied by the very last row
+    let names: Vec<String> = (0..20).map(|i| format!("x_{i:02}")).collect();
+    let formula = format!("{} {}", names.join(" "), "&".repeat(19));
+    assert!(sat(&formula));
+    let formula = format!("{} x_00 ! &", formula);
+    assert!(!sat(&formula));
+}
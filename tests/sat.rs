@@ -1,4 +1,9 @@
-use ready_set_boole::sat::sat;
+#![allow(clippy::bool_assert_comparison)]
+
+use ready_set_boole::{
+    sat::{all_models, all_models_projected, is_model, sat, solve},
+    Assignment, Expression, Variable,
+};
 
 #[test]
 fn test_simple_sat() {
@@ -56,3 +61,76 @@ fn test_edge_cases() {
     assert_eq!(sat("Z"), true);
 }
 
+#[test]
+fn test_solve() {
+    for formula in ["AB|", "AB&C!&", "AB>C>", "AB^C=D|", "AA!|", "door_open alarm_armed ! &"] {
+        let expr = Expression::from_formula(formula).unwrap();
+        let model = solve(&expr).unwrap();
+        assert_eq!(model.len(), expr.variables().len(), "{}", formula);
+        assert!(is_model(&expr, &model), "{}", formula);
+    }
+
+    let expr = Expression::from_formula("AB!&C&").unwrap();
+    assert_eq!(solve(&expr), Some("A=1,B=0,C=1".parse().unwrap()));
+    assert_eq!(solve(&Expression::from_formula("AA!&").unwrap()), None);
+    assert_eq!(solve(&Expression::from_formula("1").unwrap()), Some(Assignment::new()));
+}
+
+#[test]
+fn test_is_model() {
+    let expr = Expression::from_formula("AB|").unwrap();
+    assert!(is_model(&expr, &"A=1,B=0".parse().unwrap()));
+    assert!(!is_model(&expr, &"A=0,B=0".parse().unwrap()));
+    // B has no value
+    assert!(!is_model(&expr, &"A=1".parse().unwrap()));
+}
+
+fn model_count(formula: &str) -> usize {
+    let expr = Expression::from_formula(formula).unwrap();
+    let table = expr.evaluate_bitsliced().unwrap();
+    (0..table.len()).filter(|&row| table.get(row)).count()
+}
+
+#[test]
+fn test_all_models() {
+    for formula in ["AB|", "AB&C|", "AB^C=D|", "AB>BC>&", "AA!&", "AA!|"] {
+        let expr = Expression::from_formula(formula).unwrap();
+        let models: Vec<Assignment> = all_models(&expr).collect();
+        assert_eq!(models.len(), model_count(formula), "{}", formula);
+        for (i, model) in models.iter().enumerate() {
+            assert!(is_model(&expr, model), "{} {}", formula, model);
+            assert!(!models[..i].contains(model), "{} {}", formula, model);
+        }
+    }
+}
+
+#[test]
+fn test_all_models_projected() {
+    let expr = Expression::from_formula("AB|C&").unwrap();
+    let a = Variable::from('A');
+    let mut projected: Vec<String> = all_models_projected(&expr, std::slice::from_ref(&a)).map(|model| model.to_string()).collect();
+    projected.sort();
+    assert_eq!(projected, ["A=0", "A=1"]);
+
+    // C is true in every model
+    let projected: Vec<String> = all_models_projected(&expr, &[Variable::from('C')]).map(|model| model.to_string()).collect();
+    assert_eq!(projected, ["C=1"]);
+
+    // D is not in the expression: free
+    assert_eq!(all_models_projected(&expr, &[a.clone(), Variable::from('D'), a]).count(), 4);
+
+    // The empty projection has one model when satisfiable
+    assert_eq!(all_models_projected(&expr, &[]).count(), 1);
+    let contradiction = Expression::from_formula("AA!&").unwrap();
+    assert_eq!(all_models_projected(&contradiction, &[]).count(), 0);
+}
+
+#[test]
+fn test_all_models_limit() {
+    let expr = Expression::from_formula("ABCDEFGH||||||| IJKLMNOP||||||| &").unwrap();
+    assert_eq!(all_models(&expr).with_limit(10).count(), 10);
+    assert_eq!(all_models(&expr).with_limit(0).count(), 0);
+    // Lazy: taking a few models of a huge set is immediate
+    assert_eq!(all_models(&expr).take(3).count(), 3);
+    assert_eq!(Expression::from_formula("AB^").map(|expr| all_models(&expr).with_limit(5).count()), Ok(2));
+}
@@ -0,0 +1,118 @@
+use ready_set_boole::{
+    product_of_sums, sum_of_products,
+    truth_table::{RowOrder, StepLabel, TableFormat, TruthTable},
+    Expression, LogicError, Variable,
+};
+
+fn table(formula: &str) -> TruthTable {
+    TruthTable::from_formula(formula).unwrap()
+}
+
+fn letters(names: &str) -> Vec<Variable> {
+    names.chars().map(Variable::from).collect()
+}
+
+#[test]
+fn test_minterms_and_maxterms() {
+    let table = table("AB&C|");
+    assert_eq!(table.minterms(), [1, 3, 5, 6, 7]);
+    assert_eq!(table.maxterms(), [0, 2, 4]);
+}
+
+#[test]
+fn test_sum_of_products() {
+    assert_eq!(sum_of_products(&letters("AB"), &[1, 2]).to_rpn(), "A!B&AB!&|");
+    assert_eq!(sum_of_products(&letters("ABC"), &[5]).to_rpn(), "AB!C&&");
+    assert_eq!(sum_of_products(&letters("AB"), &[]), Expression::val(false));
+}
+
+#[test]
+fn test_product_of_sums() {
+    assert_eq!(product_of_sums(&letters("AB"), &[0, 3]).to_rpn(), "AB|A!B!|&");
+    assert_eq!(product_of_sums(&letters("AB"), &[]), Expression::val(true));
+}
+
+#[test]
+fn test_canonical_forms_are_equivalent() {
+    for formula in ["AB&C|", "AB^", "AB>C=", "AB|C&D!^", "A!B!&"] {
+        let expr = Expression::from_formula(formula).unwrap();
+        let table = TruthTable::from_expression(&expr).unwrap();
+        assert!(table.to_canonical_dnf().is_equivalent(&expr).unwrap(), "{}", formula);
+        assert!(table.to_canonical_cnf().is_equivalent(&expr).unwrap(), "{}", formula);
+    }
+}
+
+#[test]
+fn test_from_minterms() {
+    let table = TruthTable::from_minterms(letters("AB"), &[1, 2]).unwrap();
+    assert_eq!(table, self::table("AB^"));
+
+    let table = TruthTable::from_maxterms(letters("AB"), &[0]).unwrap();
+    assert_eq!(table, self::table("AB|"));
+
+    assert_eq!(
+        TruthTable::from_minterms(letters("AB"), &[4]),
+        Err(LogicError::InvalidRow { row: 4, row_count: 4 })
+    );
+}
+
+#[test]
+fn test_markdown_round_trip() {
+    for formula in ["AB&C|", "AB^", "A!", "door_open alarm & !"] {
+        let table = table(formula);
+        assert_eq!(TruthTable::from_markdown(&table.to_markdown()).unwrap(), table, "{}", formula);
+    }
+}
+
+#[test]
+fn test_markdown_gray_and_steps() {
+    let table = table("AB&C|");
+    let gray = table.render_ordered(TableFormat::Markdown, RowOrder::Gray);
+    assert_eq!(TruthTable::from_markdown(&gray).unwrap(), table);
+
+    let expr = Expression::from_formula("AB&C|").unwrap();
+    let with_steps = TruthTable::from_expression_with_steps(&expr, StepLabel::Infix).unwrap();
+    assert_eq!(TruthTable::from_markdown(&with_steps.to_markdown()).unwrap(), table);
+}
+
+#[test]
+fn test_markdown_or_steps() {
+    // The "A | B" column is written with an escaped bar
+    let table = table("AB|C&");
+    let expr = Expression::from_formula("AB|C&").unwrap();
+    for label in [StepLabel::Infix, StepLabel::Rpn] {
+        let with_steps = TruthTable::from_expression_with_steps(&expr, label).unwrap();
+        assert_eq!(TruthTable::from_markdown(&with_steps.to_markdown()).unwrap(), table);
+    }
+}
+
+#[test]
+fn test_markdown_synthesis() {
+    let markdown = "\
+| A | B | = |
+|---|---|---|
+| 0 | 0 | 0 |
+| 0 | 1 | 1 |
+| 1 | 0 | 1 |
+| 1 | 1 | 0 |
+";
+    let table = TruthTable::from_markdown(markdown).unwrap();
+    assert_eq!(table.to_canonical_dnf().to_rpn(), "A!B&AB!&|");
+    assert_eq!(table.to_canonical_cnf().to_rpn(), "AB|A!B!|&");
+}
+
+#[test]
+fn test_markdown_errors() {
+    let malformed = |markdown: &str| match TruthTable::from_markdown(markdown) {
+        Err(LogicError::Malformed { line, .. }) => line,
+        other => panic!("expected a malformed table, got {:?}", other),
+    };
+
+    assert_eq!(malformed(""), 1);
+    assert_eq!(malformed("A | = |"), 1);
+    assert_eq!(malformed("| A | = |\n|---|---|\n| 0 | 1 |\n| 2 | 0 |"), 4);
+    assert_eq!(malformed("| A | = |\n|---|---|\n| 0 | 1 |\n| 0 | 0 |"), 4);
+    assert_eq!(malformed("| A | = |\n|---|---|\n| 0 | 1 | 1 |"), 3);
+    assert_eq!(malformed("| A | B | = |\n|---|---|---|\n| 0 | 0 | 1 |"), 3);
+    assert_eq!(malformed("| A | A | = |\n"), 1);
+}
@@ -0,0 +1,214 @@
+use ready_set_boole::{
+    truth_table::{RowOrder, StepLabel, TableFormat, TruthTable},
+    Expression,
+};
+
+fn table(formula: &str) -> TruthTable {
+    TruthTable::from_formula(formula).unwrap()
+}
+
+#[test]
+fn test_rows() {
+    let table = table("AB&C|");
+    assert_eq!(table.row_count(), 8);
+    let outputs: Vec<bool> = table.rows().map(|row| row.output).collect();
+    assert_eq!(outputs, [false, true, false, true, false, true, true, true]);
+
+    let row = table.row(6);
+    assert_eq!(row.values(), [true, true, false]);
+    assert!(row.output);
+    assert_eq!(table.output().count_ones(), 5);
+}
+
+#[test]
+fn test_gray_order() {
+    let indices: Vec<u64> = table("AB^").rows_ordered(RowOrder::Gray).map(|row| row.index).collect();
+    assert_eq!(indices, [0b00, 0b01, 0b11, 0b10]);
+}
+
+#[test]
+fn test_markdown() {
+    let expected = "\
+| A | B | = |
+|---|---|---|
+| 0 | 0 | 0 |
+| 0 | 1 | 0 |
+| 1 | 0 | 0 |
+| 1 | 1 | 1 |
+";
+    assert_eq!(table("AB&").to_markdown(), expected);
+}
+
+#[test]
+fn test_markdown_named_variables() {
+    let expected = "\
+| alarm | door_open | = |
+|-------|-----------|---|
+|   0   |     0     | 1 |
+|   0   |     1     | 0 |
+|   1   |     0     | 1 |
+|   1   |     1     | 1 |
+";
+    assert_eq!(table("door_open alarm >").to_markdown(), expected);
+}
+
+#[test]
+fn test_csv() {
+    assert_eq!(table("A!").to_csv(), "A,=\n0,1\n1,0\n");
+}
+
+#[test]
+fn test_html() {
+    let expected = "\
+<table>
+  <thead>
+    <tr><th>A</th><th>=</th></tr>
+  </thead>
+  <tbody>
+    <tr><td>0</td><td>1</td></tr>
+    <tr><td>1</td><td>0</td></tr>
+  </tbody>
+</table>
+";
+    assert_eq!(table("A!").to_html(), expected);
+}
+
+#[test]
+fn test_latex() {
+    let expected = "\
+\\begin{tabular}{cc||c}
+$A$ & \\texttt{x\\_1} & $=$ \\\\
+\\hline
+0 & 0 & 0 \\\\
+0 & 1 & 1 \\\\
+1 & 0 & 1 \\\\
+1 & 1 & 1 \\\\
+\\end{tabular}
+";
+    assert_eq!(table("A x_1 |").to_latex(), expected);
+}
+
+#[test]
+fn test_text() {
+    let expected = "\
+A door | =
+0 0    | 0
+0 1    | 1
+1 0    | 1
+1 1    | 0
+";
+    assert_eq!(table("A door ^").to_text(), expected);
+}
+
+#[test]
+fn test_constant_table() {
+    let table = table("10|");
+    assert_eq!(table.row_count(), 1);
+    assert_eq!(table.render(TableFormat::Markdown), "| = |\n|---|\n| 1 |\n");
+    assert_eq!(table.render(TableFormat::Text), "| =\n| 1\n");
+}
+
+#[test]
+fn test_invalid_formula() {
+    assert!(TruthTable::from_formula("AB").is_err());
+}
+
+#[test]
+fn test_steps_infix() {
+    let expr = Expression::from_formula("AB&AB&!|").unwrap();
+    let table = TruthTable::from_expression_with_steps(&expr, StepLabel::Infix).unwrap();
+
+    // A & B appears twice but gets a single column, the root is the output
+    let labels: Vec<&str> = table.steps().iter().map(|step| step.label.as_str()).collect();
+    assert_eq!(labels, ["A & B", "!(A & B)"]);
+
+    let expected = "\
+| A | B | A & B | !(A & B) | = |
+|---|---|-------|----------|---|
+| 0 | 0 |   0   |    1     | 1 |
+| 0 | 1 |   0   |    1     | 1 |
+| 1 | 0 |   0   |    1     | 1 |
+| 1 | 1 |   1   |    0     | 1 |
+";
+    assert_eq!(table.to_markdown(), expected);
+}
+
+#[test]
+fn test_steps_rpn() {
+    let expr = Expression::from_formula("AB|C>").unwrap();
+    let table = TruthTable::from_expression_with_steps(&expr, StepLabel::Rpn).unwrap();
+    assert_eq!(table.to_csv().lines().next(), Some("A,B,C,AB|,="));
+    assert_eq!(table.to_text().lines().nth(3), Some("0 1 0 | 1   0"));
+}
+
+#[test]
+fn test_steps_bottom_up() {
+    let expr = Expression::from_formula("AB^!C&D=").unwrap();
+    let table = TruthTable::from_expression_with_steps(&expr, StepLabel::Rpn).unwrap();
+    let labels: Vec<&str> = table.steps().iter().map(|step| step.label.as_str()).collect();
+    assert_eq!(labels, ["AB^", "AB^!", "AB^!C&"]);
+
+    // Every step column agrees with the subexpression alone
+    for step in table.steps() {
+        let own = TruthTable::from_expression(&step.expression).unwrap();
+        for row in table.rows() {
+            let vars = table.variables();
+            let own_row = own
+                .variables()
+                .iter()
+                .fold(0, |acc, var| (acc << 1) | row.value(vars.iter().position(|v| v == var).unwrap()) as u64);
+            assert_eq!(step.column.get(row.index), own.row(own_row).output);
+        }
+    }
+}
+
+#[test]
+fn test_steps_latex_escaping() {
+    let expr = Expression::from_formula("AB&C|").unwrap();
+    let table = TruthTable::from_expression_with_steps(&expr, StepLabel::Infix).unwrap();
+    assert!(table.to_latex().contains("$A$ & $B$ & $C$ & \\texttt{A \\& B} & $=$ \\\\"));
+    assert!(table.to_html().contains("<th>A &amp; B</th>"));
+}
+
+#[test]
+fn test_steps_markdown_escaping() {
+    let expr = Expression::from_formula("AB|C&").unwrap();
+
+    let infix = TruthTable::from_expression_with_steps(&expr, StepLabel::Infix).unwrap();
+    let expected = "\
+| A | B | C | A \\| B | = |
+|---|---|---|--------|---|
+| 0 | 0 | 0 |   0    | 0 |
+";
+    assert!(infix.to_markdown().starts_with(expected), "{}", infix.to_markdown());
+
+    let rpn = TruthTable::from_expression_with_steps(&expr, StepLabel::Rpn).unwrap();
+    let expected = "\
+| A | B | C | AB\\| | = |
+|---|---|---|------|---|
+| 0 | 0 | 0 |  0   | 0 |
+";
+    assert!(rpn.to_markdown().starts_with(expected), "{}", rpn.to_markdown());
+}
+
+#[test]
+fn test_steps_shared_subtrees() {
+    // 2^10 copies of A & B, one column per level
+    let mut expr = Expression::from_formula("AB&").unwrap();
+    for _ in 0..10 {
+        expr = Expression::Xor(Box::new(expr.clone()), Box::new(!expr));
+    }
+    let table = TruthTable::from_expression_with_steps(&expr, StepLabel::Rpn).unwrap();
+    assert_eq!(table.steps().len(), 20);
+    assert_eq!(table.steps()[0].label, "AB&");
+    assert_eq!(table.steps()[1].label, "AB&!");
+}
+
+#[test]
+fn test_steps_long_chain() {
+    let formula = format!("A{}", "!".repeat(2000));
+    let expr = Expression::from_formula(&formula).unwrap();
+    let table = TruthTable::from_expression_with_steps(&expr, StepLabel::Rpn).unwrap();
+    assert_eq!(table.steps().len(), 1999);
+    assert_eq!(table.steps()[1998].column.iter().collect::<Vec<_>>(), [true, false]);
+}
@@ -0,0 +1,156 @@
+use ready_set_boole::{conjunctive_normal_form_with, sat::sat, Assignment, Cnf, CnfEncoding, Expression, Literal, Variable};
+
+mod common;
+use common::satisfiable;
+
+fn expr(formula: &str) -> Expression {
+    Expression::from_formula(formula).unwrap()
+}
+
+#[test]
+fn test_literal() {
+    let literal = Literal::negative(3);
+    assert_eq!(literal.variable(), 3);
+    assert!(literal.is_negated());
+    assert_eq!(!literal, Literal::positive(3));
+    assert_eq!(literal.index(), 7);
+    assert!(literal.value(false));
+}
+
+#[test]
+fn test_definitions() {
+    let tseitin = expr("AB&C|").to_tseitin();
+    assert_eq!(tseitin.original_variables(), [Variable::from('A'), Variable::from('B'), Variable::from('C')]);
+    assert_eq!(tseitin.auxiliary_variables(), [Variable::new("_t0"), Variable::new("_t1")]);
+    assert_eq!(tseitin.subexpression(&Variable::new("_t0")), Some(&expr("AB&C|")));
+    assert_eq!(tseitin.subexpression(&Variable::new("_t1")), Some(&expr("AB&")));
+    assert_eq!(tseitin.subexpression(&Variable::from('A')), None);
+
+    // 3 clauses per operator and the unit clause of the root
+    assert_eq!(tseitin.cnf().clauses().len(), 7);
+    assert_eq!(tseitin.cnf().clauses().last(), Some(&vec![Literal::positive(3)]));
+}
+
+#[test]
+fn test_shared_subexpressions_and_negations() {
+    // A & B appears twice, negations cost nothing
+    let tseitin = expr("AB&!AB&C|&").to_tseitin();
+    assert_eq!(tseitin.auxiliary_variables().len(), 3);
+}
+
+#[test]
+fn test_fresh_names_avoid_the_expression() {
+    let tseitin = Expression::from_infix("_t0 & _t1 | A").unwrap().to_tseitin();
+    let names: Vec<String> = tseitin.auxiliary_variables().iter().map(Variable::to_string).collect();
+    assert_eq!(names, ["_t2", "_t3"]);
+}
+
+#[test]
+fn test_equisatisfiable() {
+    for formula in ["AB&C|", "AA!&", "AB^A!B!^&", "AB>BA>&AB=!&", "AB=C^D>", "1", "0", "A0|", "A!1&"] {
+        let tseitin = expr(formula).to_tseitin();
+        assert_eq!(satisfiable(tseitin.cnf()), sat(formula), "{}", formula);
+    }
+}
+
+#[test]
+fn test_models_extend() {
+    // Every assignment of the original variables, with each auxiliary variable
+    // set to the value of its subexpression, satisfies the CNF exactly when
+    // it satisfies the expression
+    for formula in ["AB&C|", "AB^C=", "AB>C!&D|"] {
+        let expression = expr(formula);
+        let tseitin = expression.to_tseitin();
+        let originals = tseitin.original_variables();
+
+        for row in 0..1u64 << originals.len() {
+            let mut assignment = Assignment::new();
+            for (i, var) in originals.iter().enumerate() {
+                assignment.set(var.clone(), (row >> i) & 1 == 1);
+            }
+            let mut values: Vec<bool> = originals.iter().map(|var| assignment.get(var).unwrap()).collect();
+            for (_, subexpression) in tseitin.definitions() {
+                values.push(subexpression.evaluate(&assignment).unwrap());
+            }
+            assert_eq!(tseitin.cnf().evaluate(&values), expression.evaluate(&assignment).unwrap(), "{}", formula);
+        }
+    }
+}
+
+#[test]
+fn test_linear_size() {
+    // (a0 & b0) | (a1 & b1) | ... has 2^20 clauses once distributed
+    let terms: Vec<String> = (0..20).map(|i| format!("a{i} & b{i}")).collect();
+    let expression = Expression::from_infix(&terms.join(" | ")).unwrap();
+    let tseitin = expression.to_tseitin();
+
+    assert_eq!(tseitin.auxiliary_variables().len(), 39);
+    assert_eq!(tseitin.cnf().clauses().len(), 39 * 3 + 1);
+    assert!(tseitin.cnf().clauses().iter().all(|clause| clause.len() <= 3));
+}
+
+#[test]
+fn test_to_expression() {
+    let tseitin = expr("A!").to_tseitin();
+    assert_eq!(tseitin.cnf().clauses(), [vec![Literal::negative(0)]]);
+    assert_eq!(tseitin.cnf().to_expression().to_rpn(), "A!");
+    assert_eq!(Cnf::default().to_expression(), Expression::val(true));
+}
+
+#[test]
+fn test_plaisted_greenbaum_keeps_needed_directions() {
+    // Only positive occurrences: x => a & b and y => x | C
+    let encoded = expr("AB&C|").to_plaisted_greenbaum();
+    assert_eq!(encoded.auxiliary_variables().len(), 2);
+    assert_eq!(encoded.cnf().clauses().len(), 4);
+
+    // Under a negation the other direction is kept: !(A & B) needs A & B => x
+    let encoded = expr("AB&!").to_plaisted_greenbaum();
+    assert_eq!(encoded.cnf().clauses().len(), 2);
+    assert_eq!(encoded.cnf().clauses()[0].len(), 3);
+
+    // Operands of an equivalence are used with both signs
+    let encoded = expr("AB&C=").to_plaisted_greenbaum();
+    assert_eq!(encoded.cnf().clauses().len(), 2 + 3 + 1);
+
+    // A subexpression met with both signs gets both directions, once
+    let encoded = expr("AB&AB&!|").to_plaisted_greenbaum();
+    assert_eq!(encoded.auxiliary_variables().len(), 2);
+    assert_eq!(encoded.cnf().clauses().len(), 1 + 3 + 1);
+}
+
+#[test]
+fn test_plaisted_greenbaum_equisatisfiable() {
+    for formula in ["AB&C|", "AA!&", "AB^A!B!^&", "AB>BA>&AB=!&", "AB=C^D>", "AB&!AB&&", "1", "0", "A0|", "A!1&", "0!"] {
+        let encoded = expr(formula).to_plaisted_greenbaum();
+        let tseitin = expr(formula).to_tseitin();
+        assert_eq!(satisfiable(encoded.cnf()), sat(formula), "{}", formula);
+        assert!(encoded.cnf().clauses().len() <= tseitin.cnf().clauses().len(), "{}", formula);
+    }
+}
+
+#[test]
+fn test_cnf_encodings() {
+    assert_eq!(conjunctive_normal_form_with("ABC&|", CnfEncoding::Distributive), "AB|AC|&");
+    assert_eq!(conjunctive_normal_form_with("AB&", CnfEncoding::Tseitin), "_t0 ! A | _t0 ! B | _t0 A ! B ! | | _t0 & & &");
+    assert_eq!(conjunctive_normal_form_with("AB&", CnfEncoding::PlaistedGreenbaum), "_t0 ! A | _t0 ! B | _t0 & &");
+    assert_eq!(conjunctive_normal_form_with("A&", CnfEncoding::Tseitin), "");
+}
+
+#[test]
+fn test_long_chain_is_linear() {
+    // Cloning and dropping a tree recurse once per level, past the default stack
+    let encode = std::thread::Builder::new().stack_size(64 << 20).spawn(|| {
+        // ((x0 & x1) & x2) & ... , 10k nodes nested 5k deep
+        let mut expression = Expression::Var(Variable::new("x0"));
+        for i in 1..5000 {
+            expression = expression & Expression::Var(Variable::new(&format!("x{i}")));
+        }
+
+        let tseitin = expression.to_tseitin();
+        assert_eq!(tseitin.auxiliary_variables().len(), 4999);
+        assert_eq!(tseitin.cnf().clauses().len(), 4999 * 3 + 1);
+        assert_eq!(tseitin.definitions().count(), 4999);
+    });
+    encode.unwrap().join().unwrap();
+}
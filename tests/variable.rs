@@ -0,0 +1,64 @@
+use std::collections::HashSet;
+
+use ready_set_boole::{normalize, sat::sat, Assignment, Expression, Variable};
+
+#[test]
+fn test_letters_stay_single_characters() {
+    let expr = Expression::from_formula("AB&").unwrap();
+    assert_eq!(expr, Expression::var('A') & Expression::var('B'));
+    assert_eq!(Variable::new("A"), Variable::from('A'));
+    assert_eq!(Variable::new("A").letter(), Some('A'));
+    assert_eq!(Variable::new("x_12").letter(), None);
+}
+
+#[test]
+fn test_named_variables_in_rpn() {
+    let expr = Expression::from_formula("door_open alarm_armed ! &").unwrap();
+    assert_eq!(expr, Expression::var("door_open") & !Expression::var("alarm_armed"));
+    assert_eq!(expr.to_rpn(), "door_open alarm_armed ! &");
+
+    // Letters and names can be mixed, names only need to be separated from each other
+    let expr = Expression::from_formula("A x_12|").unwrap();
+    assert_eq!(expr, Expression::var('A') | Expression::var("x_12"));
+    assert_eq!(Expression::from_formula(&expr.to_rpn()).unwrap(), expr);
+}
+
+#[test]
+fn test_named_variables_in_infix() {
+    let expr = Expression::from_infix("(door_open ∧ ¬alarm_armed) ⇒ siren").unwrap();
+    assert_eq!(expr.to_rpn(), "door_open alarm_armed ! & siren >");
+    assert_eq!(Expression::from_infix(&expr.to_infix()).unwrap(), expr);
+}
+
+#[test]
+fn test_variables() {
+    let expr = Expression::from_formula("b a & B | a =").unwrap();
+    let expected: HashSet<Variable> = ["a", "b", "B"].into_iter().map(Variable::from).collect();
+    assert_eq!(expr.variables(), expected);
+    assert_eq!(expr.sorted_variables(), vec![Variable::from('B'), Variable::from("a"), Variable::from("b")]);
+}
+
+#[test]
+fn test_evaluate_named() {
+    let expr = Expression::from_formula("door_open alarm_armed &").unwrap();
+    let mut values = Assignment::new().with("door_open", true).with("alarm_armed", false);
+    assert_eq!(expr.evaluate(&values), Ok(false));
+    values.set("alarm_armed", true);
+    assert_eq!(expr.evaluate(&values), Ok(true));
+}
+
+#[test]
+fn test_sat_and_normalize_named() {
+    assert!(sat("door_open alarm_armed &"));
+    assert!(!sat("door_open door_open ! &"));
+    let expr = Expression::from_formula("door_open alarm_armed | !").unwrap();
+    assert_eq!(normalize(&expr).to_rpn(), "door_open ! alarm_armed ! &");
+}
+
+#[test]
+fn test_more_than_26_variables() {
+    let names: Vec<String> = (0..30).map(|i| format!("x_{i}")).collect();
+    let formula = format!("{} {}", names.join(" "), "&".repeat(29));
+    let expr = Expression::from_formula(&formula).unwrap();
+    assert_eq!(expr.variables().len(), 30);
+}